@@ -0,0 +1,218 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid stored in row-major order.
+///
+/// Cells are addressed as `(row, column)`, the same convention as
+/// `pathfinding::matrix::Matrix`. Indexing with a single `usize` returns a
+/// whole row, so `grid[y][x]` works like it does for `Vec<Vec<T>>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// An error raised when the input does not describe a rectangular grid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row has a different length than the first row.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {}",
+                row + 1,
+                found,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl Grid<char> {
+    /// Parses each line of the input into a row of characters.
+    pub fn from_chars(input: &str) -> Result<Self, GridError> {
+        Self::parse(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses each line of the input into a row, converting every character
+    /// with `cell`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The puzzle input, one row per line.
+    /// * `cell` - Converts a character into a cell.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Grid<T>, GridError>` - The grid, or an error if the rows differ in length.
+    ///
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::RaggedRow {
+                        row: height,
+                        expected,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `(row, column)`, or `None` outside the grid.
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        if row < self.height && column < self.width {
+            self.cells.get(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero size, which an empty grid would have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the `(row, column)` of the first cell matching `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| (index / self.width, index % self.width))
+    }
+
+    /// Consumes the grid, returning its cells in row-major order.
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
+
+    fn index(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self[row][column]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self[row][column]
+    }
+}
+
+#[cfg(feature = "pathfinding")]
+impl<T: Clone> From<Grid<T>> for pathfinding::matrix::Matrix<T> {
+    fn from(grid: Grid<T>) -> Self {
+        pathfinding::matrix::Matrix::from_vec(grid.height, grid.width, grid.cells)
+            .expect("grid dimensions match its cells")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_chars() {
+        let grid = Grid::from_chars("#.\n.#\n.^\n").unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[(2, 1)], '^');
+        assert_eq!(grid[0], ['#', '.']);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.position(|&c| c == '^'), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let grid = Grid::parse("01\n98", |c| c.to_digit(10).unwrap()).unwrap();
+        let rows: Vec<&[u32]> = grid.rows().collect();
+        assert_eq!(rows, vec![&[0, 1][..], &[9, 8][..]]);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        assert_eq!(
+            Grid::from_chars("abc\nab\n"),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut grid = Grid::from_chars("..\n..").unwrap();
+        grid[1][0] = 'X';
+        grid[(0, 1)] = 'Y';
+        assert_eq!(grid.into_cells(), vec!['.', 'Y', 'X', '.']);
+    }
+
+    #[cfg(feature = "pathfinding")]
+    #[test]
+    fn test_into_matrix() {
+        let matrix: pathfinding::matrix::Matrix<char> = Grid::from_chars("ab\ncd").unwrap().into();
+        assert_eq!(matrix[(1, 0)], 'c');
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+};
+
+/// The input file used when no path is given on the command line, relative to
+/// the task directory.
+pub const DEFAULT_INPUT: &str = "input/input.txt";
+
+/// Loads the puzzle input named by the first command line argument.
+///
+/// A path of `-` reads the input from stdin. Without an argument the input is
+/// read from [`DEFAULT_INPUT`].
+///
+/// # Returns
+///
+/// * `Result<String, std::io::Error>` - The puzzle input or an error.
+///
+pub fn load() -> io::Result<String> {
+    read_to_string(&input_path())
+}
+
+/// Returns the input path given as the first command line argument, or
+/// [`DEFAULT_INPUT`] when there is none.
+pub fn input_path() -> String {
+    std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_INPUT.to_string())
+}
+
+/// Reads the contents of the input file and returns a result of the file contents.
+///
+/// # Arguments
+///
+/// * `puzzle_path` - The path to the input file, or `-` to read from stdin.
+///
+/// # Returns
+///
+/// * `Result<String, std::io::Error>` - The contents of the file as a string or an error.
+///
+pub fn read_to_string(puzzle_path: &str) -> io::Result<String> {
+    let mut contents = String::new();
+    if puzzle_path == "-" {
+        io::stdin().read_to_string(&mut contents)?;
+    } else {
+        File::open(puzzle_path)?.read_to_string(&mut contents)?;
+    }
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_to_string() {
+        let path = std::env::temp_dir().join("aoc-common-input-test.txt");
+        std::fs::write(&path, "1 2\n3 4\n").unwrap();
+        let contents = read_to_string(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(contents, "1 2\n3 4\n");
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let error = read_to_string("input/does-not-exist.txt").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
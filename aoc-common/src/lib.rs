@@ -0,0 +1,12 @@
+//! Helpers shared by the daily puzzle solutions: loading the puzzle input,
+//! parsing character grids, splitting input into sections and pulling
+//! integers out of free-form text. The [`Solution`] trait describes a day's
+//! puzzle so that it can be run in-process.
+
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod solution;
+
+pub use grid::{Grid, GridError};
+pub use solution::{Answer, Solution};
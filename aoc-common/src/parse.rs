@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+/// Splits the input into sections separated by blank lines.
+///
+/// Trailing newlines and Windows line endings are tolerated, and empty
+/// sections are skipped.
+///
+/// # Arguments
+///
+/// * `input` - The puzzle input.
+///
+/// # Returns
+///
+/// * `Vec<&str>` - Each section without its surrounding blank lines.
+///
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            if let Some(section_start) = start.take() {
+                sections.push(&input[section_start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(section_start) = start {
+        sections.push(&input[section_start..end]);
+    }
+    sections
+}
+
+/// Extracts every integer from the input, ignoring any other characters.
+///
+/// A `-` directly in front of digits is read as a sign, so `p=-3,4` yields
+/// `[-3, 4]`, unless it follows another number, so the range `3-4` yields
+/// `[3, 4]`.
+///
+/// # Arguments
+///
+/// * `input` - The text to search for integers.
+///
+/// # Returns
+///
+/// * `Vec<T>` - The integers in the order they appear.
+///
+pub fn integers<T: FromStr>(input: &str) -> Vec<T> {
+    let bytes = input.as_bytes();
+    let mut integers = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let signed = bytes[index] == b'-'
+            && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+            && !(index > 0 && bytes[index - 1].is_ascii_digit());
+        if !signed && !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        integers.extend(input[start..index].parse::<T>().ok());
+    }
+    integers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let input = "47|53\n97|13\n\n75,47,61\n97,61\n";
+        assert_eq!(sections(input), vec!["47|53\n97|13", "75,47,61\n97,61"]);
+    }
+
+    #[test]
+    fn test_sections_with_crlf_and_extra_blank_lines() {
+        let input = "a\r\nb\r\n\r\n\r\nc\r\n\r\n";
+        assert_eq!(sections(input), vec!["a\r\nb", "c"]);
+    }
+
+    #[test]
+    fn test_integers() {
+        let robot: Vec<i32> = integers("p=0,4 v=3,-3");
+        assert_eq!(robot, vec![0, 4, 3, -3]);
+    }
+
+    #[test]
+    fn test_integers_skips_lone_signs() {
+        let values: Vec<u64> = integers("Register A: 729\nProgram: 0,1,5,4 - done");
+        assert_eq!(values, vec![729, 0, 1, 5, 4]);
+    }
+
+    #[test]
+    fn test_integers_reads_dashes_between_numbers_as_separators() {
+        assert_eq!(integers::<i32>("3-4"), vec![3, 4]);
+        assert_eq!(integers::<i32>("p=-3,4"), vec![-3, 4]);
+        assert_eq!(integers::<i32>("1--2"), vec![1, -2]);
+    }
+}
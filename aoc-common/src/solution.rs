@@ -0,0 +1,70 @@
+/// A day's puzzle, split into parsing and the two parts so that a runner can
+/// call it in-process and time each step.
+///
+/// Each part of a day lives in its own `dayN/taskM` crate with its own parser,
+/// so each part has its own input type, and running a part only parses the
+/// input the way that part needs it.
+pub trait Solution {
+    /// The puzzle input as part one reads it.
+    type Input1;
+    /// The puzzle input as part two reads it, or `()` for days whose part two
+    /// has not been solved.
+    type Input2;
+
+    /// Parses the puzzle input for part one, panicking if it is malformed.
+    fn parse1(input: &str) -> Self::Input1;
+
+    /// Parses the puzzle input for part two, panicking if it is malformed.
+    fn parse2(input: &str) -> Self::Input2;
+
+    /// Solves part one and returns the answer.
+    fn part1(input: Self::Input1) -> Answer;
+
+    /// Solves part two and returns the answer, or `None` if the part has not
+    /// been solved yet.
+    fn part2(_input: Self::Input2) -> Option<Answer> {
+        None
+    }
+}
+
+/// The answer to one part of a puzzle, along with a picture of how it was
+/// solved for days where one is useful.
+pub struct Answer {
+    pub answer: String,
+    /// Draws the picture. It is only called once the part has been timed, so
+    /// drawing does not count towards the solve time.
+    visualisation: Option<Box<dyn FnOnce() -> String>>,
+}
+
+impl Answer {
+    pub fn new(answer: impl ToString) -> Answer {
+        Answer {
+            answer: answer.to_string(),
+            visualisation: None,
+        }
+    }
+
+    pub fn with_visualisation(mut self, draw: impl FnOnce() -> String + 'static) -> Answer {
+        self.visualisation = Some(Box::new(draw));
+        self
+    }
+
+    /// Draws the picture, if the part has one.
+    pub fn visualise(&mut self) -> Option<String> {
+        self.visualisation.take().map(|draw| draw())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualisation_is_drawn_once() {
+        let mut answer = Answer::new(42).with_visualisation(|| "#.#".to_string());
+        assert_eq!(answer.answer, "42");
+        assert_eq!(answer.visualise().as_deref(), Some("#.#"));
+        assert_eq!(answer.visualise(), None);
+        assert_eq!(Answer::new("x").visualise(), None);
+    }
+}
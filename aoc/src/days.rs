@@ -0,0 +1,405 @@
+//! One [`Solution`] per day, joining the `dayN/task1` and `dayN/task2` crates.
+//!
+//! Each part keeps its own crate's parser, so running a part only parses the
+//! input the way that part reads it.
+
+use aoc_common::{Answer, Solution};
+use std::collections::HashMap;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input1 = (Vec<i32>, Vec<i32>);
+    type Input2 = (Vec<i32>, Vec<i32>);
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day1_task1::parse(input.to_string()).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day1_task2::parse(input.to_string()).unwrap()
+    }
+
+    fn part1(lists: Self::Input1) -> Answer {
+        Answer::new(day1_task1::solve(lists))
+    }
+
+    fn part2(lists: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day1_task2::solve(lists)))
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input1 = Vec<day2_task1::Report>;
+    type Input2 = Vec<day2_task2::Report>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day2_task1::parse(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day2_task2::parse(input)
+    }
+
+    fn part1(reports: Self::Input1) -> Answer {
+        Answer::new(day2_task1::safe_count(reports))
+    }
+
+    fn part2(reports: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day2_task2::safe_count(reports)))
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input1 = Vec<day3_task1::Instruction>;
+    type Input2 = Vec<day3_task2::Instruction>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day3_task1::parse(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day3_task2::parse(input)
+    }
+
+    fn part1(instructions: Self::Input1) -> Answer {
+        Answer::new(day3_task1::run_instructions(instructions))
+    }
+
+    fn part2(instructions: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day3_task2::run_instructions(instructions)))
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input1 = day4_task1::Puzzle;
+    type Input2 = day4_task2::Puzzle;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day4_task1::parse_puzzle(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day4_task2::parse_puzzle(input)
+    }
+
+    fn part1(puzzle: Self::Input1) -> Answer {
+        Answer::new(day4_task1::solve(&puzzle))
+    }
+
+    fn part2(puzzle: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day4_task2::solve(&puzzle)))
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input1 = (day5_task1::Rules, day5_task1::Pages);
+    type Input2 = (day5_task2::Rules, day5_task2::Pages);
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day5_task1::parse_input(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day5_task2::parse_input(input)
+    }
+
+    fn part1(rules_and_pages: Self::Input1) -> Answer {
+        Answer::new(day5_task1::solve(rules_and_pages))
+    }
+
+    fn part2(rules_and_pages: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day5_task2::solve(rules_and_pages)))
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input1 = day6_task1::Map;
+    type Input2 = day6_task2::Map;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day6_task1::parse_input(input).expect("Failed to parse input")
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day6_task2::parse_input(input).expect("Failed to parse input")
+    }
+
+    fn part1(mut map: Self::Input1) -> Answer {
+        let visited = day6_task1::solve(&mut map);
+        Answer::new(visited).with_visualisation(move || map.to_string())
+    }
+
+    fn part2(map: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day6_task2::solve(map)))
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input1 = Vec<day7_task1::Equation>;
+    type Input2 = Vec<day7_task2::Equation>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day7_task1::parse_input(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day7_task2::parse_input(input).unwrap()
+    }
+
+    fn part1(equations: Self::Input1) -> Answer {
+        Answer::new(day7_task1::solve(equations))
+    }
+
+    fn part2(equations: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day7_task2::solve(equations)))
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input1 = day8_task1::Map;
+    type Input2 = day8_task2::Map;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day8_task1::parse_input(input).expect("Failed to parse input")
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day8_task2::parse_input(input).expect("Failed to parse input")
+    }
+
+    fn part1(map: Self::Input1) -> Answer {
+        let count = day8_task1::solve(&map);
+        Answer::new(count).with_visualisation(move || map.to_string())
+    }
+
+    fn part2(map: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day8_task2::solve(&map)))
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input1 = day9_task1::Disk;
+    type Input2 = day9_task2::Disk;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day9_task1::parse_input(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day9_task2::parse_input(input).unwrap()
+    }
+
+    fn part1(disk: Self::Input1) -> Answer {
+        Answer::new(day9_task1::solve(disk))
+    }
+
+    fn part2(disk: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day9_task2::solve(disk)))
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input1 = day10_task1::Map;
+    type Input2 = day10_task2::Map;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day10_task1::parse(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day10_task2::parse(input).unwrap()
+    }
+
+    fn part1(map: Self::Input1) -> Answer {
+        Answer::new(day10_task1::solve(&map))
+    }
+
+    fn part2(map: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day10_task2::solve(&map)))
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input1 = Vec<u64>;
+    type Input2 = HashMap<u64, u64>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day11_task1::parse(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day11_task2::parse(input).unwrap()
+    }
+
+    fn part1(stones: Self::Input1) -> Answer {
+        Answer::new(day11_task1::solve(stones))
+    }
+
+    fn part2(stones: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day11_task2::solve(stones)))
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input1 = day12_task1::Map;
+    type Input2 = day12_task2::Map;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day12_task1::parse(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day12_task2::parse(input).unwrap()
+    }
+
+    fn part1(map: Self::Input1) -> Answer {
+        Answer::new(day12_task1::solve(map))
+    }
+
+    fn part2(map: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day12_task2::solve(map)))
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input1 = Vec<day13_task1::PrizeMachine>;
+    type Input2 = Vec<day13_task2::PrizeMachine>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day13_task1::parse_input(input).unwrap()
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day13_task2::parse_input(input).unwrap()
+    }
+
+    fn part1(prize_machines: Self::Input1) -> Answer {
+        Answer::new(day13_task1::solve(prize_machines))
+    }
+
+    fn part2(prize_machines: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day13_task2::solve(prize_machines)))
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input1 = Vec<day14_task1::Robot>;
+    type Input2 = Vec<day14_task2::Robot>;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day14_task1::parse(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day14_task2::parse(input)
+    }
+
+    fn part1(mut robots: Self::Input1) -> Answer {
+        use day14_task1::{X_BOUND, Y_BOUND};
+        day14_task1::simulate(&mut robots, 100, X_BOUND, Y_BOUND);
+        Answer::new(day14_task1::safety_factor(robots.clone(), X_BOUND, Y_BOUND))
+            .with_visualisation(move || day14_task1::render_robot_map(&robots, X_BOUND, Y_BOUND))
+    }
+
+    fn part2(robots: Self::Input2) -> Option<Answer> {
+        use day14_task2::{X_BOUND, Y_BOUND};
+        let tree_frame = day14_task2::find_tree_frame(&robots, X_BOUND, Y_BOUND);
+        Some(Answer::new(tree_frame).with_visualisation(move || {
+            let positions: Vec<_> = robots
+                .iter()
+                .map(|robot| robot.simulate(tree_frame, X_BOUND, Y_BOUND))
+                .collect();
+            day14_task2::render_robot_map(&positions, X_BOUND, Y_BOUND)
+        }))
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input1 = (day15_task1::Map, day15_task1::Directions);
+    type Input2 = ();
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day15_task1::parse(input)
+    }
+
+    fn parse2(_input: &str) -> Self::Input2 {}
+
+    fn part1((mut map, directions): Self::Input1) -> Answer {
+        day15_task1::run_directions(&mut map, directions);
+        Answer::new(day15_task1::box_gps_sum(&map)).with_visualisation(move || map.render())
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input1 = day16_task1::Map;
+    type Input2 = day16_task2::Map;
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day16_task1::parse(input)
+    }
+
+    fn parse2(input: &str) -> Self::Input2 {
+        day16_task2::parse(input)
+    }
+
+    fn part1(map: Self::Input1) -> Answer {
+        let (paths, lowest_score) = day16_task1::find_best_paths(&map);
+        let answer = Answer::new(lowest_score);
+        match paths.into_iter().next() {
+            Some(path) => answer.with_visualisation(move || map.render_path(path)),
+            None => answer,
+        }
+    }
+
+    fn part2(map: Self::Input2) -> Option<Answer> {
+        Some(Answer::new(day16_task2::solve(&map)))
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input1 = day17_task1::Computer;
+    type Input2 = ();
+
+    fn parse1(input: &str) -> Self::Input1 {
+        day17_task1::parse(input)
+    }
+
+    fn parse2(_input: &str) -> Self::Input2 {}
+
+    fn part1(computer: Self::Input1) -> Answer {
+        Answer::new(day17_task1::solve(computer))
+    }
+}
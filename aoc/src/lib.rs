@@ -0,0 +1,140 @@
+//! The registry of daily solutions, so that any of them can be run in-process
+//! rather than by building and launching the task's own binary.
+
+pub mod days;
+
+use aoc_common::{Answer, Solution};
+use days::*;
+use std::time::{Duration, Instant};
+
+/// The structured result of running one part of a day.
+pub struct RunResult {
+    pub answer: String,
+    /// A rendering of the puzzle, for days that provide one.
+    pub visualisation: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Runs one part of a day's solution on the puzzle input, panicking if the
+/// input is malformed.
+pub type Runner = fn(input: &str) -> RunResult;
+
+/// Every solved part, in order of day and then part.
+pub const SOLUTIONS: &[(u8, u8, Runner)] = &[
+    (1, 1, run::<Day1, 1>),
+    (1, 2, run::<Day1, 2>),
+    (2, 1, run::<Day2, 1>),
+    (2, 2, run::<Day2, 2>),
+    (3, 1, run::<Day3, 1>),
+    (3, 2, run::<Day3, 2>),
+    (4, 1, run::<Day4, 1>),
+    (4, 2, run::<Day4, 2>),
+    (5, 1, run::<Day5, 1>),
+    (5, 2, run::<Day5, 2>),
+    (6, 1, run::<Day6, 1>),
+    (6, 2, run::<Day6, 2>),
+    (7, 1, run::<Day7, 1>),
+    (7, 2, run::<Day7, 2>),
+    (8, 1, run::<Day8, 1>),
+    (8, 2, run::<Day8, 2>),
+    (9, 1, run::<Day9, 1>),
+    (9, 2, run::<Day9, 2>),
+    (10, 1, run::<Day10, 1>),
+    (10, 2, run::<Day10, 2>),
+    (11, 1, run::<Day11, 1>),
+    (11, 2, run::<Day11, 2>),
+    (12, 1, run::<Day12, 1>),
+    (12, 2, run::<Day12, 2>),
+    (13, 1, run::<Day13, 1>),
+    (13, 2, run::<Day13, 2>),
+    (14, 1, run::<Day14, 1>),
+    (14, 2, run::<Day14, 2>),
+    (15, 1, run::<Day15, 1>),
+    (16, 1, run::<Day16, 1>),
+    (16, 2, run::<Day16, 2>),
+    (17, 1, run::<Day17, 1>),
+];
+
+/// Looks up the solution registered for a part of a day.
+pub fn solution(day: u8, part: u8) -> Option<Runner> {
+    SOLUTIONS
+        .iter()
+        .find(|(registered_day, registered_part, _)| {
+            *registered_day == day && *registered_part == part
+        })
+        .map(|(_, _, runner)| *runner)
+}
+
+fn run<S: Solution, const PART: u8>(input: &str) -> RunResult {
+    match PART {
+        1 => timed(input, S::parse1, S::part1),
+        _ => timed(input, S::parse2, |parsed| {
+            S::part2(parsed).expect("only solved parts are registered")
+        }),
+    }
+}
+
+/// Parses the input and solves the part, timing each step. The
+/// visualisation is drawn after the clock has stopped.
+fn timed<I>(input: &str, parse: fn(&str) -> I, solve: impl FnOnce(I) -> Answer) -> RunResult {
+    let start = Instant::now();
+    let parsed = parse(input);
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut answer = solve(parsed);
+    let solve_time = start.elapsed();
+
+    RunResult {
+        visualisation: answer.visualise(),
+        answer: answer.answer,
+        parse_time,
+        solve_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY1_EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn test_run_day1() {
+        assert_eq!(solution(1, 1).unwrap()(DAY1_EXAMPLE).answer, "11");
+        assert_eq!(solution(1, 2).unwrap()(DAY1_EXAMPLE).answer, "31");
+        assert!(solution(1, 3).is_none());
+    }
+
+    #[test]
+    fn test_unsolved_part() {
+        let input = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n";
+        let result = solution(17, 1).unwrap()(input);
+        assert_eq!(result.answer, "4,6,3,5,6,3,5,2,1,0");
+        assert!(result.visualisation.is_none());
+        assert!(solution(17, 2).is_none());
+    }
+
+    #[test]
+    fn test_visualisation() {
+        let input = "..#\n.^.\n...\n";
+        let result = solution(6, 1).unwrap()(input);
+        assert_eq!(result.answer, "2");
+        let visualisation = result.visualisation.unwrap();
+        assert_eq!(visualisation.lines().count(), 3);
+        assert!(solution(6, 2).unwrap()(input).visualisation.is_none());
+    }
+
+    #[test]
+    fn test_unregistered_day() {
+        assert!(solution(25, 1).is_none());
+    }
+
+    #[test]
+    fn test_solutions_are_unique_and_ordered() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1)));
+    }
+}
@@ -0,0 +1,93 @@
+use serde_json::json;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: aoc run [--message-format=json] <day> <part> [input]
+       aoc list
+
+Runs a solution in-process and prints its answer. The input defaults to
+dayN/taskM/input/input.txt, and a path of - reads the input from stdin.
+
+With --message-format=json, the answer, the visualisation and the timings
+are printed on one line as a JSON object instead.
+
+list prints the day and part of every registered solution, one per line.";
+
+/// How the result of a run is printed.
+#[derive(Clone, Copy, PartialEq)]
+enum MessageFormat {
+    Human,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["list"] => {
+            for (day, part, _) in aoc::SOLUTIONS {
+                println!("{} {}", day, part);
+            }
+            Ok(())
+        }
+        ["run", "--message-format=json", day, part] => run(day, part, None, MessageFormat::Json),
+        ["run", "--message-format=json", day, part, input] => {
+            run(day, part, Some(input), MessageFormat::Json)
+        }
+        ["run", day, part] => run(day, part, None, MessageFormat::Human),
+        ["run", day, part, input] => run(day, part, Some(input), MessageFormat::Human),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(day: &str, part: &str, input: Option<&str>, format: MessageFormat) -> Result<(), String> {
+    let day: u8 = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+    let part: u8 = match part.parse() {
+        Ok(part @ (1 | 2)) => part,
+        _ => return Err(format!("Invalid part: {}, expected 1 or 2", part)),
+    };
+    let runner = aoc::solution(day, part)
+        .ok_or_else(|| format!("Day {} part {} has no registered solution", day, part))?;
+
+    let input_path = match input {
+        Some(path) => path.to_string(),
+        None => format!(
+            "day{}/task{}/{}",
+            day,
+            part,
+            aoc_common::input::DEFAULT_INPUT
+        ),
+    };
+    let puzzle_input = aoc_common::input::read_to_string(&input_path)
+        .map_err(|e| format!("Failed to read {}: {}", input_path, e))?;
+
+    let result = runner(&puzzle_input);
+    if format == MessageFormat::Json {
+        let message = json!({
+            "reason": "solved",
+            "answer": result.answer,
+            "visualisation": result.visualisation,
+            "parse_time": result.parse_time.as_secs_f64(),
+            "solve_time": result.solve_time.as_secs_f64(),
+        });
+        println!("{}", message);
+        return Ok(());
+    }
+    println!("{}", result.answer);
+    eprintln!(
+        "Day {} part {} parsed in {:?}, solved in {:?}",
+        day, part, result.parse_time, result.solve_time
+    );
+    Ok(())
+}
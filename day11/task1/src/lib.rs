@@ -0,0 +1,85 @@
+//! Day 11: Plutonian Pebbles.
+//!
+//! Counts the stones after blinking 25 times, where every blink changes or
+//! splits each stone according to its number.
+
+pub fn parse(puzzle_input: &str) -> Result<Vec<u64>, std::num::ParseIntError> {
+    let first_line = puzzle_input.lines().next().unwrap();
+    first_line.split_whitespace().map(|s| s.parse()).collect()
+}
+
+pub fn apply_rules_to_stones(stones: Vec<u64>) -> Vec<u64> {
+    stones
+        .iter()
+        .flat_map(|&stone| apply_rules(stone))
+        .collect()
+}
+
+fn apply_rules(stone: u64) -> Vec<u64> {
+    // Rule 1
+    if stone == 0 {
+        return vec![1];
+    }
+
+    // Rule 2
+    let digits = stone.ilog10() + 1;
+    let even_digits = digits.is_multiple_of(2);
+    if even_digits {
+        let half = digits / 2;
+        let left = stone / 10u64.pow(half);
+        let right = stone % 10u64.pow(half);
+        return vec![left, right];
+    }
+
+    // Rule 3
+    vec![stone * 2024]
+}
+
+/// Blinks 25 times and counts the resulting stones.
+pub fn solve(mut stones: Vec<u64>) -> usize {
+    for _ in 0..25 {
+        stones = apply_rules_to_stones(stones);
+    }
+    stones.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PUZZLE_INPUT: &str = r#"125 17"#;
+
+    #[test]
+    fn test_parse() {
+        let expected = vec![125, 17];
+        let result = parse(PUZZLE_INPUT).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_apply_rules_to_stones() {
+        let stones = vec![125, 17];
+        let expected_iterations = vec![
+            vec![125, 17],
+            vec![253000, 1, 7],
+            vec![253, 0, 2024, 14168],
+            vec![512072, 1, 20, 24, 28676032],
+            vec![512, 72, 2024, 2, 0, 2, 4, 2867, 6032],
+            vec![1036288, 7, 2, 20, 24, 4048, 1, 4048, 8096, 28, 67, 60, 32],
+            vec![
+                2097446912, 14168, 4048, 2, 0, 2, 4, 40, 48, 2024, 40, 48, 80, 96, 2, 8, 6, 7, 6,
+                0, 3, 2,
+            ],
+        ];
+
+        // Run through the iterations
+        let mut iterations = vec![stones];
+        for _ in 0..expected_iterations.len() - 1 {
+            let last = iterations.last().unwrap().clone();
+            let next = apply_rules_to_stones(last);
+            iterations.push(next);
+        }
+
+        assert_eq!(iterations, expected_iterations);
+    }
+}
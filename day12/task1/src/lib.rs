@@ -0,0 +1,163 @@
+//! Day 12: Garden Groups.
+//!
+//! Sums the price of fencing every region of the garden, which is its area
+//! multiplied by its perimeter.
+
+use aoc_common::{Grid, GridError};
+use pathfinding::prelude::Matrix;
+use std::char;
+use std::collections::{HashMap, HashSet};
+
+/// The garden, with the type of plant growing at each position.
+pub type Map = Matrix<char>;
+
+pub struct Plot {
+    pub id: char,
+
+    // Coordinates of the plot. These are (row, col) coordinates.
+    pub coords: Vec<(usize, usize)>,
+
+    pub area: usize,
+    pub perimeter: usize,
+}
+
+/// Parses the input file and returns a 2d array of chars
+/// If the input file is not formatted correctly, the function will return an
+/// error as a result.
+///
+/// # Arguments
+///
+/// * `puzzle_input` - A string containing the contents of the input file.
+///
+/// # Returns
+///
+/// A 2d array of positive integers.
+///
+pub fn parse(puzzle_input: &str) -> Result<Matrix<char>, GridError> {
+    Grid::from_chars(puzzle_input).map(Matrix::from)
+}
+
+pub fn find_plots_dimensions(map: Matrix<char>) -> Vec<Plot> {
+    let mut plots = vec![];
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+
+    for point in map.keys() {
+        if visited.contains(&point) {
+            // Skip visited plots
+            continue;
+        }
+
+        // Find the plots connected to this point.
+        let start_char = map.get(point).expect("Invalid point");
+        let reachable = map.bfs_reachable(point, false, |coord| {
+            let c = map.get(coord).expect("Invalid point");
+            c == start_char
+        });
+        visited.extend(reachable.iter().cloned());
+        let coords: Vec<(usize, usize)> = reachable.iter().cloned().collect();
+        let area = plot_area(&coords);
+        let perimeter = plot_perimeter(&coords);
+        let plot = Plot {
+            id: *start_char,
+            coords,
+            area,
+            perimeter,
+        };
+        plots.push(plot);
+    }
+
+    plots
+}
+
+fn plot_area(coords: &[(usize, usize)]) -> usize {
+    coords.len()
+}
+
+/// Calculates the perimeter of a plot. This is the edges of the plot that are
+/// not shared with another plot. A 2x2 plot has a perimeter of 8.
+fn plot_perimeter(coords: &[(usize, usize)]) -> usize {
+    let mut perimeter = 0;
+
+    for (row, col) in coords {
+        let mut neighbours = vec![];
+        neighbours.push((row + 1, *col));
+        neighbours.push((*row, col + 1));
+        if *row > 0 {
+            neighbours.push((row - 1, *col));
+        }
+        if *col > 0 {
+            neighbours.push((*row, col - 1));
+        }
+
+        let mut neighbour_count = 0;
+        for (n_row, n_col) in neighbours {
+            if coords.contains(&(n_row, n_col)) {
+                neighbour_count += 1;
+            }
+        }
+        let perimeter_diff = 4 - neighbour_count;
+        perimeter += perimeter_diff;
+    }
+
+    perimeter
+}
+
+pub fn print_plot_shape(plot: &Plot) {
+    let mut plot_map: HashMap<(usize, usize), char> = HashMap::new();
+    for (row, col) in &plot.coords {
+        plot_map.insert((*row, *col), plot.id);
+    }
+
+    let (min_row, max_row) = plot
+        .coords
+        .iter()
+        .fold((usize::MAX, usize::MIN), |acc, (row, _)| {
+            (acc.0.min(*row), acc.1.max(*row))
+        });
+    let (min_col, max_col) = plot
+        .coords
+        .iter()
+        .fold((usize::MAX, usize::MIN), |acc, (_, col)| {
+            (acc.0.min(*col), acc.1.max(*col))
+        });
+
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            let c = plot_map.get(&(row, col)).unwrap_or(&'.');
+            print!("{}", c);
+        }
+        println!();
+    }
+}
+
+/// Calculates the total price of fencing all plots, where the price of a
+/// plot is its area multiplied by its perimeter.
+pub fn solve(map: Matrix<char>) -> usize {
+    find_plots_dimensions(map)
+        .iter()
+        .map(|plot| plot.area * plot.perimeter)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static PUZZLE_INPUT: &str = r#"AAAA
+BBCD
+BBCC
+EEEC"#;
+
+    #[test]
+    fn test_parse() {
+        let result = parse(PUZZLE_INPUT).unwrap();
+        let expected = Matrix::from_rows(vec![
+            vec!['A', 'A', 'A', 'A'],
+            vec!['B', 'B', 'C', 'D'],
+            vec!['B', 'B', 'C', 'C'],
+            vec!['E', 'E', 'E', 'C'],
+        ])
+        .unwrap();
+        assert_eq!(result, expected);
+    }
+}
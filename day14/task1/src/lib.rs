@@ -0,0 +1,299 @@
+//! Day 14: Restroom Redoubt.
+//!
+//! Moves the robots for 100 seconds and multiplies the number of robots in
+//! each quadrant to get the safety factor.
+
+use aoc_common::parse::integers;
+use colored::*;
+use itertools::Itertools;
+use pathfinding::prelude::Matrix;
+use std::fmt::Write;
+
+// The size of the space the robots move around in.
+pub const X_BOUND: i32 = 101;
+pub const Y_BOUND: i32 = 103;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Robot {
+    x: i32,
+    y: i32,
+    dx: i32,
+    dy: i32,
+}
+
+impl Robot {
+    fn step(&mut self, x_bound: i32, y_bound: i32) {
+        self.x += self.dx;
+        self.y += self.dy;
+
+        // Implement wrapping around the bounds
+        if self.x < 0 {
+            self.x += x_bound;
+        } else if self.x >= x_bound {
+            self.x -= x_bound;
+        }
+
+        if self.y < 0 {
+            self.y += y_bound;
+        } else if self.y >= y_bound {
+            self.y -= y_bound;
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Vec<Robot> {
+    integers::<i32>(input)
+        .into_iter()
+        .tuples()
+        .map(|(x, y, dx, dy)| Robot { x, y, dx, dy })
+        .collect()
+}
+
+// Split the robots based on which of the 4 quadrants they are in.
+fn split_into_quadrants(robots: Vec<Robot>, x_bound: i32, y_bound: i32) -> Vec<Vec<Robot>> {
+    let mut quadrants = vec![vec![]; 4];
+    for robot in robots {
+        if robot.x < x_bound / 2 && robot.y < y_bound / 2 {
+            quadrants[0].push(robot);
+        } else if robot.x >= x_bound / 2 && robot.y < y_bound / 2 {
+            quadrants[1].push(robot);
+        } else if robot.x < x_bound / 2 && robot.y >= y_bound / 2 {
+            quadrants[2].push(robot);
+        } else {
+            quadrants[3].push(robot);
+        }
+    }
+    quadrants
+}
+
+// Robots that are perfectly on a quadrant boundary are filtered out.
+fn filter_middle_robots(robots: Vec<Robot>, x_bound: i32, y_bound: i32) -> Vec<Robot> {
+    robots
+        .into_iter()
+        .filter(|robot| robot.x != x_bound / 2 && robot.y != y_bound / 2)
+        .collect()
+}
+
+/// Draws the robots in each quadrant, leaving out the middle lines.
+pub fn render_robot_map(robots: &[Robot], x_bound: i32, y_bound: i32) -> String {
+    let mut out = String::new();
+    let filtered_robots = filter_middle_robots(robots.to_vec(), x_bound, y_bound);
+
+    let matrix = Matrix::from_fn(y_bound as usize, x_bound as usize, |(y, x)| {
+        filtered_robots
+            .iter()
+            .filter(|robot| robot.x == x as i32 && robot.y == y as i32)
+            .count()
+    });
+
+    // Draw the x-axis labels
+    write!(out, "   ").unwrap();
+    for x in 0..x_bound {
+        write!(out, "{:2} ", x).unwrap();
+    }
+    writeln!(out).unwrap();
+
+    for y in 0..y_bound {
+        // Draw the y-axis label
+        write!(out, "{:2}  ", y).unwrap();
+
+        // Skip the middle line
+        if y == y_bound / 2 {
+            writeln!(out).unwrap();
+            continue;
+        }
+
+        for x in 0..x_bound {
+            // Skip the middle column
+            if x == x_bound / 2 {
+                write!(out, "   ").unwrap();
+                continue;
+            }
+
+            let c = match matrix[(y as usize, x as usize)] {
+                0 => ".".to_string(),
+                n => n.to_string(),
+            };
+
+            let colored_c = if x < x_bound / 2 && y < y_bound / 2 {
+                c.green()
+            } else if x >= x_bound / 2 && y < y_bound / 2 {
+                c.red()
+            } else if x < x_bound / 2 && y >= y_bound / 2 {
+                c.blue()
+            } else {
+                c.yellow()
+            };
+
+            write!(out, "{:2} ", colored_c).unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+    out
+}
+
+pub fn print_robot_map(robots: &[Robot], x_bound: i32, y_bound: i32) {
+    print!("{}", render_robot_map(robots, x_bound, y_bound));
+}
+
+// Move every robot forward by the given number of seconds.
+pub fn simulate(robots: &mut [Robot], seconds: usize, x_bound: i32, y_bound: i32) {
+    for _ in 0..seconds {
+        robots
+            .iter_mut()
+            .for_each(|robot| robot.step(x_bound, y_bound));
+    }
+}
+
+// Multiply together the number of robots in each quadrant, ignoring the
+// robots on the middle lines.
+pub fn safety_factor(robots: Vec<Robot>, x_bound: i32, y_bound: i32) -> usize {
+    let filtered_robots = filter_middle_robots(robots, x_bound, y_bound);
+    let quadrants = split_into_quadrants(filtered_robots, x_bound, y_bound);
+    quadrants
+        .iter()
+        .fold(1, |acc, quadrant| quadrant.len() * acc)
+}
+
+pub fn solve(mut robots: Vec<Robot>) -> usize {
+    simulate(&mut robots, 100, X_BOUND, Y_BOUND);
+    safety_factor(robots, X_BOUND, Y_BOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE_INPUT: &str = r#"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3"#;
+
+    #[test]
+    fn test_parse() {
+        let input = "1 2 3 4\n5 6 7 8";
+        let expected = vec![
+            Robot {
+                x: 1,
+                y: 2,
+                dx: 3,
+                dy: 4,
+            },
+            Robot {
+                x: 5,
+                y: 6,
+                dx: 7,
+                dy: 8,
+            },
+        ];
+        assert_eq!(parse(input), expected);
+    }
+
+    #[test]
+    fn test_parse2() {
+        let input = PUZZLE_INPUT;
+        let expected = [
+            Robot {
+                x: 0,
+                y: 4,
+                dx: 3,
+                dy: -3,
+            },
+            Robot {
+                x: 6,
+                y: 3,
+                dx: -1,
+                dy: -3,
+            },
+            Robot {
+                x: 10,
+                y: 3,
+                dx: -1,
+                dy: 2,
+            },
+            Robot {
+                x: 2,
+                y: 0,
+                dx: 2,
+                dy: -1,
+            },
+            Robot {
+                x: 0,
+                y: 0,
+                dx: 1,
+                dy: 3,
+            },
+            Robot {
+                x: 3,
+                y: 0,
+                dx: -2,
+                dy: -2,
+            },
+            Robot {
+                x: 7,
+                y: 6,
+                dx: -1,
+                dy: -3,
+            },
+            Robot {
+                x: 3,
+                y: 0,
+                dx: -1,
+                dy: -2,
+            },
+            Robot {
+                x: 9,
+                y: 3,
+                dx: 2,
+                dy: 3,
+            },
+            Robot {
+                x: 7,
+                y: 3,
+                dx: -1,
+                dy: 2,
+            },
+            Robot {
+                x: 2,
+                y: 4,
+                dx: 2,
+                dy: -3,
+            },
+            Robot {
+                x: 9,
+                y: 5,
+                dx: -3,
+                dy: -3,
+            },
+        ];
+        assert_eq!(parse(input), expected);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut robot = Robot {
+            x: 0,
+            y: 0,
+            dx: 1,
+            dy: 1,
+        };
+        robot.step(10, 10);
+        assert_eq!(
+            robot,
+            Robot {
+                x: 1,
+                y: 1,
+                dx: 1,
+                dy: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Day 15: Warehouse Woes.
+//!
+//! Moves the robot around the warehouse, pushing boxes as it goes, then sums
+//! the GPS coordinates of every box.
+
+use aoc_common::parse::sections;
+use aoc_common::Grid;
+use colored::Colorize;
+use itertools::Itertools;
+use pathfinding::matrix::directions;
+use pathfinding::prelude::Matrix;
+use std::fmt::Write;
+
+pub struct Map {
+    map: Matrix<char>,
+
+    // The col, row position of the robot. This is the position of the robot
+    // in the matrix.
+    position: (usize, usize),
+}
+
+const FREE_SPACE: char = '.';
+const BOX: char = 'O';
+const WALL: char = '#';
+
+impl Map {
+    fn new(map: Matrix<char>, position: (usize, usize)) -> Self {
+        Self { map, position }
+    }
+
+    /// Moves the robot in the specified direction. The robot can move in any
+    /// of the four cardinal directions (up, down, left, right).
+    ///
+    /// The robot can only move into a free space or push a box into a free space.
+    /// When the robot pushes a box, the box is moved into the free space. The
+    /// robot can push multiple boxes at once as long as there is a free space
+    /// behind the boxes. If there is a wall behind the chain of boxes, the robot
+    /// cannot push the boxes.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The direction in which the robot should move. The direction
+    ///   can be one of the four cardinal directions: up, down, left, right represented
+    ///   by the `Direction` enum.
+    ///
+    fn move_direction(&mut self, direction: Direction) {
+        let new_position = self.map.move_in_direction(self.position, direction);
+        if new_position.is_none() {
+            // For some reason, the new position is out of bounds. This should
+            // not be possible as the wall should have prevented the robot from
+            // moving out of bounds.
+            println!(
+                "{}",
+                "The robot is trying to move out of bounds.".red().bold()
+            );
+            return;
+        }
+
+        let new_position = new_position.unwrap();
+
+        let value = self.map.get(new_position);
+        match value {
+            Some(&FREE_SPACE) => self.move_to_free_space(new_position),
+            Some(&BOX) => self.move_to_box(new_position, direction),
+            Some(&WALL) => (),
+            Some(_) => {
+                // The robot is trying to move into an invalid cell.
+                println!(
+                    "{}",
+                    "The robot is trying to move into an invalid cell."
+                        .red()
+                        .bold()
+                );
+            }
+            None => (),
+        }
+    }
+
+    /// Moves the robot into the free space by updating the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - A tuple of the new position of the robot. The tuple is a
+    ///   pair of the column and row of the new position.
+    ///
+    fn move_to_free_space(&mut self, to: (usize, usize)) {
+        self.position = to;
+    }
+
+    /// Moves the robot to the box and pushes the box into the free space.
+    /// The robot moves into the free space after pushing the box.
+    ///
+    /// It is possible to push multiple boxes at once as long as there is a free
+    /// space behind the boxes. If there is a wall behind the chain of boxes, the
+    /// robot cannot push the boxes.
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - A tuple of the new position of the robot. The tuple is a
+    ///   pair of the column and row of the new position.
+    /// * `direction` - The direction in which the robot should move. The direction
+    ///   can be one of the four cardinal directions: up, down, left, right represented
+    ///   by the `Direction` enum.
+    ///
+    fn move_to_box(&mut self, to: (usize, usize), direction: Direction) {
+        let mut free_space = None;
+        let iter = self.map.in_direction(to, direction);
+        for coord in iter {
+            let value = self.map.get(coord);
+            match value {
+                Some(&FREE_SPACE) => {
+                    free_space = Some(coord);
+                    break;
+                }
+                Some(&BOX) => (),
+                Some(&WALL) => break,
+                None => break,
+                Some(_) => (),
+            }
+        }
+
+        if let Some(free_space) = free_space {
+            // We found a free space behind the box. Push all the boxes
+            // along in that direction. We can simulate this by swapping
+            // the box with the free space. Then we move the robot forward.
+            self.map.swap(to, free_space);
+            self.position = to;
+        }
+    }
+
+    fn find_all_boxes(&self) -> Vec<(usize, usize)> {
+        self.map
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(x, &cell)| if cell == BOX { Some((x, y)) } else { None })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// Draws the map with the robot, boxes and walls in colour.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.map.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                let colored_cell = if (x, y) == (self.position.1, self.position.0) {
+                    '@'.to_string().yellow().bold()
+                } else {
+                    match cell {
+                        FREE_SPACE => FREE_SPACE.to_string().white(),
+                        BOX => BOX.to_string().blue(),
+                        WALL => WALL.to_string().red(),
+                        _ => cell.to_string().normal(),
+                    }
+                };
+                write!(out, "{}", colored_cell).unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+        out
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+/// Calculates the CPS value of the position. This is calculated by (100 * row) + column.
+fn gps_value(position: &(usize, usize)) -> usize {
+    let (col, row) = position;
+    (100 * row) + col
+}
+
+pub type Direction = (isize, isize);
+pub type Directions = Vec<Direction>;
+
+pub fn parse(input: &str) -> (Map, Directions) {
+    let (map_str, directions_str) = sections(input)
+        .into_iter()
+        .collect_tuple()
+        .expect("Invalid input format");
+    let grid = Grid::from_chars(map_str).expect("Invalid matrix");
+    let position = grid.position(|&cell| cell == '@').expect("Robot not found");
+    let mut matrix = Matrix::from(grid);
+    matrix[position] = '.'; // Overwrite the robot position with a free space.
+    let map = Map::new(matrix, position);
+    let directions = directions_str
+        .chars()
+        .filter_map(|line| match line {
+            '^' => Some(directions::N),
+            'v' => Some(directions::S),
+            '<' => Some(directions::W),
+            '>' => Some(directions::E),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    (map, directions)
+}
+
+/// Moves the robot in each of the directions in turn, pushing any boxes in
+/// its way.
+pub fn run_directions(map: &mut Map, directions: Directions) {
+    directions.into_iter().for_each(|direction| {
+        map.move_direction(direction);
+    });
+}
+
+/// Sums the GPS values of all the boxes on the map.
+pub fn box_gps_sum(map: &Map) -> usize {
+    map.find_all_boxes().iter().map(gps_value).sum()
+}
+
+/// Moves the robot through all of its directions and sums the GPS values of
+/// the boxes where they end up.
+pub fn solve((mut map, directions): (Map, Directions)) -> usize {
+    run_directions(&mut map, directions);
+    box_gps_sum(&map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE_INPUT: &str = r#"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
+
+    #[test]
+    fn test_parse() {
+        let (map, directions) = parse(PUZZLE_INPUT);
+        assert_eq!(map.position, (4, 4));
+        assert_eq!(directions.len(), 700);
+        assert_eq!(map.map.columns, 10);
+        assert_eq!(map.map.rows, 10);
+    }
+}
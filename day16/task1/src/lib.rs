@@ -0,0 +1,218 @@
+//! Day 16: Reindeer Maze.
+//!
+//! Finds the lowest score a reindeer can get from the start to the end tile,
+//! where each step costs 1 point and each turn 1000 points.
+
+use aoc_common::Grid;
+use colored::Colorize;
+use pathfinding::prelude::Matrix;
+use pathfinding::{matrix::directions, prelude::astar_bag_collect};
+use std::fmt::Write;
+
+/// Point in the matrix represenitng a (col, row).
+pub type Point = (usize, usize);
+
+pub type Direction = (isize, isize);
+
+/// Converts a direction tuple to a corresponding character.
+///
+/// # Arguments
+///
+/// * `direction` - A tuple representing the direction.
+///
+/// # Returns
+///
+/// * `char` - The character representing the direction.
+fn convert_direction(direction: Direction) -> char {
+    match direction {
+        directions::N => '^',
+        directions::S => 'v',
+        directions::E => '>',
+        directions::W => '<',
+        _ => '#',
+    }
+}
+
+pub struct Map {
+    map: Matrix<char>,
+
+    // The col, row position of the reindeer and its destination.
+    position: Point,
+    start: Point,
+    end: Point,
+
+    // The direction the reindeer is facing.
+    direction: Direction,
+}
+
+const FREE_SPACE: char = '.';
+const START: char = 'S';
+const END: char = 'E';
+const WALL: char = '#';
+
+impl Map {
+    /// Creates a new `Map` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - A `Matrix<char>` representing the map.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new `Map` instance.
+    fn new(map: Matrix<char>) -> Self {
+        let start_post = Self::find_char(&map, START).expect("Start not found");
+        let end_post = Self::find_char(&map, END).expect("End not found");
+        Self {
+            map,
+            position: start_post,
+            start: start_post,
+            end: end_post,
+            direction: directions::E,
+        }
+    }
+
+    /// Finds the position of a character in the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - A reference to the `Matrix<char>`.
+    /// * `c` - The character to find.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(usize, usize)>` - The position of the character, or `None` if not found.
+    fn find_char(map: &Matrix<char>, c: char) -> Option<(usize, usize)> {
+        for (col, row_chars) in map.iter().enumerate() {
+            for (row, &cell) in row_chars.iter().enumerate() {
+                if cell == c {
+                    return Some((col, row));
+                }
+            }
+        }
+        None
+    }
+
+    /// Calculates the weight of changing direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The current direction.
+    /// * `new` - The new direction.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The weight of changing direction.
+    fn direction_weight(current: Direction, new: Direction) -> usize {
+        match (current, new) {
+            (directions::N, directions::N) => 1, // Same direction
+            (directions::S, directions::S) => 1,
+            (directions::E, directions::E) => 1,
+            (directions::W, directions::W) => 1,
+            (directions::N, directions::E) => 1001, // Clockwise
+            (directions::E, directions::S) => 1001,
+            (directions::S, directions::W) => 1001,
+            (directions::W, directions::N) => 1001,
+            (directions::N, directions::W) => 1001, // Counter clockwise
+            (directions::W, directions::S) => 1001,
+            (directions::S, directions::E) => 1001,
+            (directions::E, directions::N) => 1001,
+            (directions::N, directions::S) => 2001, // Turn around
+            (directions::S, directions::N) => 2001,
+            (directions::E, directions::W) => 2001,
+            (directions::W, directions::E) => 2001,
+            _ => panic!("Invalid direction"),
+        }
+    }
+
+    /// Draws the path on the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A vector of tuples containing points and directions.
+    pub fn render_path(&self, path: Vec<(Point, Direction)>) -> String {
+        let mut out = String::new();
+        let mut map = self.map.clone();
+        path.iter()
+            .for_each(|(pos, dir)| map[*pos] = convert_direction(*dir));
+        for (y, row) in map.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                let colored_cell = if (x, y) == (self.position.1, self.position.0) {
+                    '@'.to_string().yellow().bold()
+                } else {
+                    match cell {
+                        FREE_SPACE => FREE_SPACE.to_string().white(),
+                        START => START.to_string().green(),
+                        END => END.to_string().green(),
+                        WALL => WALL.to_string().red(),
+                        'X' => 'X'.to_string().blue(),
+                        _ => cell.to_string().normal(),
+                    }
+                };
+                write!(out, "{}", colored_cell).unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+        out
+    }
+
+    /// Prints the path on the map.
+    pub fn print_path(&self, path: Vec<(Point, Direction)>) {
+        print!("{}", self.render_path(path));
+    }
+}
+
+/// Parses the input string into a `Map` instance.
+///
+/// # Arguments
+///
+/// * `input` - A string slice containing the input.
+///
+/// # Returns
+///
+/// * `Map` - The parsed `Map` instance.
+pub fn parse(input: &str) -> Map {
+    let grid = Grid::from_chars(input).expect("Invalid matrix");
+    Map::new(Matrix::from(grid))
+}
+
+/// Finds every lowest scoring path through the maze. Moving forward costs 1
+/// and every turn costs 1000.
+///
+/// # Arguments
+///
+/// * `map` - The parsed `Map` instance.
+///
+/// # Returns
+///
+/// * `(Vec<Vec<(Point, Direction)>>, usize)` - The best paths and their score.
+pub fn find_best_paths(map: &Map) -> (Vec<Vec<(Point, Direction)>>, usize) {
+    let start = (map.start, map.direction);
+    let successors = |&(pos, dir): &(Point, Direction)| {
+        let mut successors = Vec::new();
+        for &new_dir in directions::DIRECTIONS_4.iter() {
+            if let Some(new_pos) = map.map.move_in_direction(pos, new_dir) {
+                if map.map[new_pos] == WALL {
+                    continue;
+                }
+                let weight = Map::direction_weight(dir, new_dir);
+                successors.push(((new_pos, new_dir), weight));
+            }
+        }
+        successors
+    };
+    astar_bag_collect(&start, successors, |_| 1, |&(pos, _)| pos == map.end).unwrap()
+}
+
+/// Finds the lowest score a reindeer could get.
+///
+/// # Arguments
+///
+/// * `map` - The parsed `Map` instance.
+///
+/// # Returns
+///
+/// * `usize` - The score of the best path.
+pub fn solve(map: &Map) -> usize {
+    find_best_paths(map).1
+}
@@ -0,0 +1,72 @@
+//! Day 3: Mull It Over.
+//!
+//! Scans the corrupted memory for valid `mul(a,b)` instructions and sums the
+//! results of the multiplications.
+
+use regex::Regex;
+
+pub type Instruction = (i32, i32);
+
+/// Parses the puzzle input and returns a vector of Instruction tuples.
+/// Each tuple contains the two numbers that need to be multiplied together.
+///
+/// An instruction can be parsed by reading a 'mul(a, b)' where a and b
+/// are numbers that can be 1-3 digits long.
+///
+/// # Arguments
+///
+/// * `puzzle_input` - A string slice that holds the puzzle input.
+///
+/// # Returns
+///
+/// * `Vec<Instruction>` - A vector of instructions, where each Instruction is
+///   a tuple of integers.
+///
+pub fn parse(puzzle_input: &str) -> Vec<Instruction> {
+    // Use regex to find the instances of 'mul(a, b)' in the puzzle input.
+    let re = Regex::new(r"mul\((\d{1,3}),\s*(\d{1,3})\)").unwrap();
+    re.captures_iter(puzzle_input)
+        .map(|cap| {
+            (
+                cap[1].parse::<i32>().unwrap(),
+                cap[2].parse::<i32>().unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// Runs the instructions and returns the result of the multiplication of the two numbers.
+///
+/// # Arguments
+///
+/// * `instructions` - A vector of instructions, where each Instruction is a tuple of integers.
+///
+/// # Returns
+///
+/// * `i32` - The result of the multiplication of the two numbers in the instructions.
+///
+pub fn run_instructions(instructions: Vec<Instruction>) -> i32 {
+    instructions.iter().fold(0, |acc, (a, b)| acc + a * b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let puzzle_input =
+            r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#;
+        let expected = vec![(2, 4), (5, 5), (11, 8), (8, 5)];
+        assert_eq!(parse(puzzle_input), expected);
+    }
+
+    #[test]
+    fn test_run_instructions() {
+        let instructions = vec![(2, 4), (5, 5), (11, 8), (8, 5)];
+        assert_eq!(
+            run_instructions(instructions),
+            161 // 2 * 4 + 5 * 5 + 11 * 8 + 8 * 5
+        );
+    }
+}
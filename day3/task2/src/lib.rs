@@ -0,0 +1,125 @@
+//! Day 3: Mull It Over, part two.
+//!
+//! Sums the multiplications as in part one, but `don't()` disables the
+//! `mul` instructions that follow it until a `do()` enables them again.
+
+use regex::Regex;
+
+/// An enum representing different types of instructions.
+///
+/// The `Instruction` enum has three variants:
+/// - `Mul(i32, i32)`: Represents a multiplication operation with two operands.
+/// - `Do()`: An enable flag to indicate that future instructions should be processed.
+/// - `Dont()`: A disable flag to indicate that future instructions should not be processed.
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Mul(i32, i32),
+    Do(),
+    Dont(),
+}
+
+/// Parses the puzzle input and returns a vector of Instruction tuples.
+/// Each tuple contains the two numbers that need to be multiplied together.
+///
+/// An instruction can be parsed by reading a 'mul(a, b)' where a and b
+/// are numbers that can be 1-3 digits long.
+///
+/// # Arguments
+///
+/// * `puzzle_input` - A string slice that holds the puzzle input.
+///
+/// # Returns
+///
+/// * `Vec<Instruction>` - A vector of instructions, where each Instruction is
+///   a tuple of integers.
+pub fn parse(puzzle_input: &str) -> Vec<Instruction> {
+    // Use regex to find the instances of 'mul(a, b)' in the puzzle input.
+    let re = Regex::new(r"(mul\((\d{1,3}),\s*(\d{1,3})\)|do\(\)|don't\(\))").unwrap();
+    re.captures_iter(puzzle_input)
+        .map(|cap| {
+            match &cap[0] {
+                "do()" => Instruction::Do(),
+                "don't()" => Instruction::Dont(),
+                _ => {
+                    // Parse the two numbers from the nested capture group.
+                    let a = cap[2].parse().unwrap();
+                    let b = cap[3].parse().unwrap();
+                    Instruction::Mul(a, b)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Runs the instructions and returns the result of the multiplication.
+///
+/// The instructions are processed in order, and the result is calculated by
+/// multiplying the two numbers together.
+///
+/// The Do instruction enables the processing of future instructions.
+/// The Dont instruction disables the processing of future instructions.
+///
+/// # Arguments
+///
+/// * `instructions` - A vector of instructions to be processed.
+///
+/// # Returns
+///
+/// * `i32` - The result of the multiplication of the two numbers.
+///
+pub fn run_instructions(instructions: Vec<Instruction>) -> i32 {
+    let mut result = 0;
+    let mut do_flag = true;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mul(a, b) => {
+                if do_flag {
+                    result += a * b;
+                }
+            }
+            Instruction::Do() => {
+                do_flag = true;
+            }
+            Instruction::Dont() => {
+                do_flag = false;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let puzzle_input =
+            r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#;
+        let expected = vec![
+            Instruction::Mul(2, 4),
+            Instruction::Dont(),
+            Instruction::Mul(5, 5),
+            Instruction::Mul(11, 8),
+            Instruction::Do(),
+            Instruction::Mul(8, 5),
+        ];
+        assert_eq!(parse(puzzle_input), expected);
+    }
+
+    #[test]
+    fn test_run_instructions() {
+        let instructions = vec![
+            Instruction::Mul(2, 4),
+            Instruction::Dont(),
+            Instruction::Mul(5, 5),
+            Instruction::Mul(11, 8),
+            Instruction::Do(),
+            Instruction::Mul(8, 5),
+        ];
+        assert_eq!(
+            run_instructions(instructions),
+            48 // 2 * 4 + 8 * 5
+        );
+    }
+}
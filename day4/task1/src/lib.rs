@@ -0,0 +1,176 @@
+//! Day 4: Ceres Search.
+//!
+//! Counts every occurrence of XMAS in the word search, written in any of the
+//! eight directions.
+
+use aoc_common::Grid;
+
+pub type Puzzle = Grid<char>;
+
+/// Parses the puzzle input into a grid of characters.
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the puzzle input.
+///
+/// # Returns
+///
+/// A grid of characters representing the puzzle.
+pub fn parse_puzzle(input: &str) -> Puzzle {
+    Grid::from_chars(input).expect("Invalid puzzle")
+}
+
+/// Counts the occurrences of a word in the puzzle.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to a grid of characters representing the puzzle.
+/// * `word` - A string slice that holds the word to be counted.
+///
+/// # Returns
+///
+/// The number of times the word appears in the puzzle.
+fn count_word(puzzle: &Puzzle, word: &str) -> usize {
+    let reversed_word: String = word.chars().rev().collect();
+    let mut count = count_word_downwards(puzzle, word);
+    count += count_word_downwards(puzzle, &reversed_word);
+    count
+}
+
+fn count_word_downwards(puzzle: &Puzzle, word: &str) -> usize {
+    let word_chars: Vec<char> = word.chars().collect();
+    let word_len = word_chars.len();
+    let mut count = 0;
+    let first_char = word_chars[0];
+    for i in 0..puzzle.height() {
+        for j in 0..puzzle.width() {
+            if puzzle[i][j] == first_char {
+                if check_right(puzzle, &word_chars, i, j, word_len) {
+                    count += 1;
+                }
+                if check_down(puzzle, &word_chars, i, j, word_len) {
+                    count += 1;
+                }
+                if check_diagonal_down_right(puzzle, &word_chars, i, j, word_len) {
+                    count += 1;
+                }
+                if check_diagonal_down_left(puzzle, &word_chars, i, j, word_len) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+fn check_right(puzzle: &Puzzle, word_chars: &[char], i: usize, j: usize, word_len: usize) -> bool {
+    if j + word_len <= puzzle.width() {
+        for (k, &ch) in word_chars.iter().enumerate().skip(1) {
+            if puzzle[i][j + k] != ch {
+                return false;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn check_down(puzzle: &Puzzle, word_chars: &[char], i: usize, j: usize, word_len: usize) -> bool {
+    if i + word_len <= puzzle.height() {
+        for (k, &ch) in word_chars.iter().enumerate().skip(1) {
+            if puzzle[i + k][j] != ch {
+                return false;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn check_diagonal_down_right(
+    puzzle: &Puzzle,
+    word_chars: &[char],
+    i: usize,
+    j: usize,
+    word_len: usize,
+) -> bool {
+    if i + word_len <= puzzle.height() && j + word_len <= puzzle.width() {
+        for (k, &ch) in word_chars.iter().enumerate().skip(1) {
+            if puzzle[i + k][j + k] != ch {
+                return false;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn check_diagonal_down_left(
+    puzzle: &Puzzle,
+    word_chars: &[char],
+    i: usize,
+    j: usize,
+    word_len: usize,
+) -> bool {
+    if i + word_len <= puzzle.height() && j >= word_len - 1 {
+        for (k, &ch) in word_chars.iter().enumerate().skip(1) {
+            if puzzle[i + k][j - k] != ch {
+                return false;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+/// Counts the occurrences of "XMAS" in the puzzle in any direction.
+///
+/// # Arguments
+///
+/// * `puzzle` - A reference to the puzzle.
+///
+/// # Returns
+///
+/// The number of times "XMAS" appears in the puzzle.
+pub fn solve(puzzle: &Puzzle) -> usize {
+    count_word(puzzle, "XMAS")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let puzzle_input = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#;
+        let puzzle = parse_puzzle(puzzle_input);
+        assert_eq!(puzzle.height(), 10);
+        assert_eq!(puzzle.width(), 10);
+    }
+
+    #[test]
+    fn test_find_word() {
+        let puzzle_input = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#;
+        let puzzle = parse_puzzle(puzzle_input);
+        let count = count_word(&puzzle, "XMAS");
+        assert_eq!(count, 18);
+    }
+}
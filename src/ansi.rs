@@ -0,0 +1,227 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// Converts lines containing ANSI escape sequences into styled ratatui lines.
+///
+/// Only SGR sequences (`ESC [ ... m`) affect the output style. Any other CSI
+/// or OSC sequence is dropped so that cursor movement and similar codes do not
+/// leak into the preview. The current style is carried over between calls to
+/// `parse_line`, matching how a terminal keeps colours active across newlines.
+#[derive(Default)]
+pub struct AnsiParser {
+    style: Style,
+}
+
+impl AnsiParser {
+    /// Parses a single line of output, which should not contain a newline.
+    pub fn parse_line(&mut self, line: &str) -> Line<'static> {
+        let mut spans = Vec::new();
+        let mut text = String::new();
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '\x1b' {
+                text.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut final_byte = None;
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            final_byte = Some(c);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                    if final_byte == Some('m') {
+                        if !text.is_empty() {
+                            spans.push(Span::styled(std::mem::take(&mut text), self.style));
+                        }
+                        self.apply_sgr(&params);
+                    }
+                }
+                Some(']') => {
+                    // OSC sequences end with BEL or ESC \
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !text.is_empty() {
+            spans.push(Span::styled(text, self.style));
+        }
+        Line::from(spans)
+    }
+
+    /// Applies the `;` separated parameters of an SGR sequence to the current
+    /// style. An empty parameter list is treated as a reset.
+    fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u16> = params
+            .split([';', ':'])
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let mut codes = codes.into_iter();
+
+        while let Some(code) = codes.next() {
+            self.style = match code {
+                0 => Style::default(),
+                1 => self.style.add_modifier(Modifier::BOLD),
+                2 => self.style.add_modifier(Modifier::DIM),
+                3 => self.style.add_modifier(Modifier::ITALIC),
+                4 => self.style.add_modifier(Modifier::UNDERLINED),
+                5 => self.style.add_modifier(Modifier::SLOW_BLINK),
+                6 => self.style.add_modifier(Modifier::RAPID_BLINK),
+                7 => self.style.add_modifier(Modifier::REVERSED),
+                8 => self.style.add_modifier(Modifier::HIDDEN),
+                9 => self.style.add_modifier(Modifier::CROSSED_OUT),
+                22 => self.style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+                23 => self.style.remove_modifier(Modifier::ITALIC),
+                24 => self.style.remove_modifier(Modifier::UNDERLINED),
+                25 => self
+                    .style
+                    .remove_modifier(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+                27 => self.style.remove_modifier(Modifier::REVERSED),
+                28 => self.style.remove_modifier(Modifier::HIDDEN),
+                29 => self.style.remove_modifier(Modifier::CROSSED_OUT),
+                30..=37 => self.style.fg(basic_color(code - 30)),
+                38 => match extended_color(&mut codes) {
+                    Some(color) => self.style.fg(color),
+                    None => self.style,
+                },
+                39 => self.style.fg(Color::Reset),
+                40..=47 => self.style.bg(basic_color(code - 40)),
+                48 => match extended_color(&mut codes) {
+                    Some(color) => self.style.bg(color),
+                    None => self.style,
+                },
+                49 => self.style.bg(Color::Reset),
+                90..=97 => self.style.fg(bright_color(code - 90)),
+                100..=107 => self.style.bg(bright_color(code - 100)),
+                _ => self.style,
+            };
+        }
+    }
+}
+
+fn basic_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+fn bright_color(index: u16) -> Color {
+    match index {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+/// Reads the remainder of a `38;5;n` or `38;2;r;g;b` style colour.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(codes.next()? as u8)),
+        2 => {
+            let r = codes.next()? as u8;
+            let g = codes.next()? as u8;
+            let b = codes.next()? as u8;
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_line() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("no colours here");
+        assert_eq!(line, Line::from(vec![Span::raw("no colours here")]));
+    }
+
+    #[test]
+    fn test_parse_coloured_spans() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("#\x1b[31m@\x1b[0m.\x1b[1;92mO\x1b[0m");
+        assert_eq!(
+            line,
+            Line::from(vec![
+                Span::raw("#"),
+                Span::styled("@", Style::default().fg(Color::Red)),
+                Span::raw("."),
+                Span::styled(
+                    "O",
+                    Style::default()
+                        .fg(Color::LightGreen)
+                        .add_modifier(Modifier::BOLD)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_extended_colours() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("\x1b[38;5;208ma\x1b[48;2;1;2;3mb");
+        assert_eq!(
+            line,
+            Line::from(vec![
+                Span::styled("a", Style::default().fg(Color::Indexed(208))),
+                Span::styled(
+                    "b",
+                    Style::default()
+                        .fg(Color::Indexed(208))
+                        .bg(Color::Rgb(1, 2, 3))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_style_carries_across_lines() {
+        let mut parser = AnsiParser::default();
+        parser.parse_line("\x1b[34mblue");
+        let line = parser.parse_line("still blue\x1b[m");
+        assert_eq!(
+            line,
+            Line::from(vec![Span::styled(
+                "still blue",
+                Style::default().fg(Color::Blue)
+            )])
+        );
+    }
+
+    #[test]
+    fn test_non_sgr_sequences_are_dropped() {
+        let mut parser = AnsiParser::default();
+        let line = parser.parse_line("\x1b[2J\x1b[Hmap\x1b]0;title\x07");
+        assert_eq!(line, Line::from(vec![Span::raw("map")]));
+    }
+}
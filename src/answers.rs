@@ -0,0 +1,147 @@
+use aoc_common::input::DEFAULT_INPUT;
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The file in a task directory that lists the expected answer for each of
+/// its input files, one `input.txt: 1234` per line. Blank lines and lines
+/// starting with `#` are ignored.
+pub const ANSWERS_FILE: &str = "answers";
+
+/// Whether a task printed the expected answer for its input.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Correct,
+    /// The task's answer differed from the expected one, or no answer could
+    /// be found in its output.
+    Wrong {
+        expected: String,
+    },
+}
+
+impl Verdict {
+    /// A ✓ or ✗ badge to show next to the task.
+    pub fn badge(&self) -> Span<'static> {
+        match self {
+            Verdict::Correct => Span::styled(" ✓", Style::default().fg(Color::Green)),
+            Verdict::Wrong { .. } => Span::styled(" ✗", Style::default().fg(Color::Red)),
+        }
+    }
+}
+
+/// The expected answers of a task, keyed by input file name.
+#[derive(Default)]
+pub struct Answers {
+    expected: HashMap<String, String>,
+}
+
+impl Answers {
+    /// Reads the answers file of a task. A task without one has no expected
+    /// answers.
+    pub fn load(task_path: &str) -> Answers {
+        std::fs::read_to_string(Path::new(task_path).join(ANSWERS_FILE))
+            .map(|contents| Answers::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(contents: &str) -> Answers {
+        let expected = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(':'))
+            .map(|(input, answer)| (input.trim().to_string(), answer.trim().to_string()))
+            .collect();
+        Answers { expected }
+    }
+
+    /// Compares an answer with the one expected for the input, or returns
+    /// `None` if no answer is recorded for it.
+    pub fn verify(&self, input_name: &str, answer: Option<&str>) -> Option<Verdict> {
+        let expected = self.expected.get(input_name)?;
+        if answer == Some(expected.as_str()) {
+            Some(Verdict::Correct)
+        } else {
+            Some(Verdict::Wrong {
+                expected: expected.clone(),
+            })
+        }
+    }
+}
+
+/// Returns the name of the input file a task was run with, as used in the
+/// answers file. `input_file` is relative to the task directory, and tasks
+/// run without one read `input/input.txt`.
+pub fn input_name(input_file: Option<&str>) -> String {
+    let input_file = input_file.unwrap_or(DEFAULT_INPUT);
+    Path::new(input_file)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(input_file)
+        .to_string()
+}
+
+/// Finds the answer in the output of a task run through cargo, which only
+/// prints it as text. The answer is taken to be the last token containing a
+/// digit on the last non-empty line, which covers both "Count: 31" and
+/// "The tree appears after 6577 seconds".
+pub fn extract_answer(lines: &[String]) -> Option<String> {
+    let last_line = lines.iter().rev().find(|line| !line.trim().is_empty())?;
+    last_line
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .map(|token| token.trim_end_matches(['.', '!']))
+        .rfind(|token| token.chars().any(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_verify() {
+        let answers = Answers::parse("# day 1\ninput.txt: 1234\n\nexample.txt:11\n");
+        assert_eq!(
+            answers.verify("input.txt", Some("1234")),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            answers.verify("example.txt", Some("12")),
+            Some(Verdict::Wrong {
+                expected: "11".to_string()
+            })
+        );
+        assert!(matches!(
+            answers.verify("example.txt", None),
+            Some(Verdict::Wrong { .. })
+        ));
+        assert_eq!(answers.verify("other.txt", Some("1")), None);
+    }
+
+    #[test]
+    fn test_input_name() {
+        assert_eq!(input_name(None), "input.txt");
+        assert_eq!(input_name(Some("input/example.txt")), "example.txt");
+    }
+
+    #[test]
+    fn test_extract_answer() {
+        let lines = |lines: &[&str]| lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            extract_answer(&lines(&["Map:", "Count: 31", ""])),
+            Some("31".to_string())
+        );
+        assert_eq!(
+            extract_answer(&lines(&["The tree appears after 6577 seconds"])),
+            Some("6577".to_string())
+        );
+        assert_eq!(
+            extract_answer(&lines(&["Output: 4,6,3,5,6,3,5,2,1,0"])),
+            Some("4,6,3,5,6,3,5,2,1,0".to_string())
+        );
+        assert_eq!(extract_answer(&lines(&["Done"])), None);
+    }
+}
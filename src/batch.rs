@@ -0,0 +1,445 @@
+use crate::answers::{self, Answers, Verdict};
+use crate::config::{Action, KeyBindings};
+use crate::taskfinder;
+use crate::taskpreview::TaskStatus;
+use crate::taskrunner::{self, format_duration, Executable, RunStats, TaskHandle, TaskOutput};
+
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Row, Table};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::{oneshot, Semaphore};
+use tokio::task::JoinSet;
+
+/// The outcome of running a task to the end without the TUI.
+pub struct TaskResult {
+    pub day: u8,
+    pub part: u8,
+    /// The input file relative to the task directory, if not its default.
+    pub input_file: Option<String>,
+    pub status: TaskStatus,
+    pub stats: RunStats,
+    pub answer: Option<String>,
+    pub verdict: Option<Verdict>,
+    /// The task's output with ANSI escapes stripped. Build output is not
+    /// included.
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+}
+
+impl TaskResult {
+    fn new(day: u8, part: u8, input_file: Option<&str>) -> TaskResult {
+        TaskResult {
+            day,
+            part,
+            input_file: input_file.map(str::to_string),
+            status: TaskStatus::Finished(None),
+            stats: RunStats::default(),
+            answer: None,
+            verdict: None,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }
+    }
+
+    /// The time the task itself took, excluding any build: parsing and
+    /// solving for registered solutions, or the executable's run otherwise.
+    pub fn duration(&self) -> Option<Duration> {
+        match (self.stats.parse_time, self.stats.run_time) {
+            (Some(parse_time), Some(run_time)) => Some(parse_time + run_time),
+            (parse_time, run_time) => parse_time.or(run_time),
+        }
+    }
+
+    fn check(&self) -> &'static str {
+        match self.verdict {
+            Some(Verdict::Correct) => "✓",
+            Some(Verdict::Wrong { .. }) => "✗",
+            None => "",
+        }
+    }
+
+    /// Records one message from the task, returning whether it was the last.
+    fn collect(&mut self, output: TaskOutput) -> bool {
+        let (status, stats) = match output {
+            TaskOutput::Stdout(line) => {
+                self.stdout.push(strip_ansi_escapes::strip_str(&line));
+                return false;
+            }
+            TaskOutput::Stderr(line) => {
+                self.stderr.push(strip_ansi_escapes::strip_str(&line));
+                return false;
+            }
+            TaskOutput::Build(_) | TaskOutput::BuildStderr(_) | TaskOutput::Built(_) => {
+                return false
+            }
+            TaskOutput::BuildFailed(stats) => (TaskStatus::BuildFailed, stats),
+            TaskOutput::Finished(status, stats) => {
+                if status.is_some_and(|status| status.success()) {
+                    self.answer = answers::extract_answer(&self.stdout);
+                }
+                (TaskStatus::Finished(status), stats)
+            }
+            TaskOutput::Solved(answer, stats) => {
+                self.answer = Some(answer);
+                (TaskStatus::Solved, stats)
+            }
+            TaskOutput::Failed(stats) => (TaskStatus::Failed, stats),
+            TaskOutput::Cancelled(stats) => (TaskStatus::Cancelled, stats),
+        };
+        self.status = status;
+        self.stats = stats;
+        true
+    }
+}
+
+/// The formats the batch table can be exported in.
+#[derive(Clone, Copy)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+}
+
+impl ExportFormat {
+    /// The file the table is written to, in the current directory.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "run-all.md",
+            ExportFormat::Csv => "run-all.csv",
+        }
+    }
+}
+
+/// A run of every task, at most `jobs` at a time once they have all been
+/// built. Dropping it aborts the build and the tasks that have not finished,
+/// which terminates their children.
+pub struct BatchRun {
+    /// The finished tasks, ordered by day and part.
+    pub results: Vec<TaskResult>,
+    pub total: usize,
+    /// The file the table was last exported to.
+    pub exported: Option<String>,
+    /// How long the shared build took, once it has finished, including any
+    /// wait for a build that was already running.
+    pub build_time: Option<Duration>,
+    build_receiver: oneshot::Receiver<Duration>,
+    receiver: UnboundedReceiver<TaskResult>,
+    started: Instant,
+    elapsed: Option<Duration>,
+    _tasks: JoinSet<()>,
+}
+
+impl BatchRun {
+    pub fn start(tasks: Vec<(u8, u8)>, jobs: usize) -> BatchRun {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (build_sender, build_receiver) = oneshot::channel();
+        let total = tasks.len();
+
+        // Everything is built in one go first. Building each task as it
+        // starts would have the jobs queue up on cargo's build lock.
+        let mut join_set = JoinSet::new();
+        join_set.spawn(async move {
+            let build_started = Instant::now();
+            let executables = taskrunner::build_tasks(&tasks).await;
+            let _ = build_sender.send(build_started.elapsed());
+
+            let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+            let mut runs = JoinSet::new();
+            for (day, part) in tasks {
+                let sender = sender.clone();
+                let semaphore = semaphore.clone();
+                let executable = executables.get(&(day, part)).cloned();
+                runs.spawn(async move {
+                    let Ok(_permit) = semaphore.acquire_owned().await else {
+                        return;
+                    };
+                    let _ = sender.send(run_built(day, part, executable).await);
+                });
+            }
+            while runs.join_next().await.is_some() {}
+        });
+
+        BatchRun {
+            results: Vec::new(),
+            total,
+            exported: None,
+            build_time: None,
+            build_receiver,
+            receiver,
+            started: Instant::now(),
+            elapsed: None,
+            _tasks: join_set,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.results.len() == self.total
+    }
+
+    /// Picks up any results that have arrived since the last call without
+    /// blocking, so it is safe to call once per frame.
+    pub fn poll(&mut self) {
+        if let Ok(build_time) = self.build_receiver.try_recv() {
+            self.build_time = Some(build_time);
+        }
+        while let Ok(result) = self.receiver.try_recv() {
+            self.add_result(result);
+        }
+    }
+
+    /// Waits for every task to finish.
+    pub async fn wait(&mut self) {
+        while !self.is_finished() {
+            match self.receiver.recv().await {
+                Some(result) => self.add_result(result),
+                None => break,
+            }
+        }
+        self.poll();
+    }
+
+    fn add_result(&mut self, result: TaskResult) {
+        let index = self
+            .results
+            .partition_point(|r| (r.day, r.part) < (result.day, result.part));
+        self.results.insert(index, result);
+        if self.is_finished() {
+            self.elapsed = Some(self.started.elapsed());
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from("| Day | Part | Answer | Duration | Status | Check |\n");
+        table.push_str("| ---: | ---: | --- | ---: | --- | :---: |\n");
+        for result in &self.results {
+            table.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                result.day,
+                result.part,
+                result.answer.as_deref().unwrap_or(""),
+                result.duration().map(format_duration).unwrap_or_default(),
+                result.status.describe().unwrap_or_default(),
+                result.check(),
+            ));
+        }
+        table
+    }
+
+    /// Formats the table as CSV, with durations in milliseconds so that they
+    /// can be compared numerically.
+    pub fn to_csv(&self) -> String {
+        let mut table = String::from("day,part,answer,duration_ms,status,check\n");
+        for result in &self.results {
+            let fields = [
+                result.day.to_string(),
+                result.part.to_string(),
+                result.answer.clone().unwrap_or_default(),
+                result
+                    .duration()
+                    .map(|duration| format!("{:.3}", duration.as_secs_f64() * 1000.0))
+                    .unwrap_or_default(),
+                result.status.describe().unwrap_or_default(),
+                match result.verdict {
+                    Some(Verdict::Correct) => "correct".to_string(),
+                    Some(Verdict::Wrong { .. }) => "wrong".to_string(),
+                    None => String::new(),
+                },
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            table.push_str(&fields.join(","));
+            table.push('\n');
+        }
+        table
+    }
+
+    pub fn format(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Markdown => self.to_markdown(),
+            ExportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    /// Writes the table to the format's file and remembers where it went.
+    pub fn export(&mut self, format: ExportFormat) -> std::io::Result<()> {
+        std::fs::write(format.file_name(), self.format(format))?;
+        self.exported = Some(format.file_name().to_string());
+        Ok(())
+    }
+
+    pub fn render(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect, keys: &KeyBindings) {
+        let header = Row::new(["Day", "Part", "Answer", "Duration", "Status", "Check"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.results.iter().map(|result| {
+            let check = match &result.verdict {
+                Some(verdict) => Cell::from(Line::from(verdict.badge())),
+                None => Cell::from(""),
+            };
+            Row::new([
+                Cell::from(result.day.to_string()),
+                Cell::from(result.part.to_string()),
+                Cell::from(result.answer.clone().unwrap_or_default()),
+                Cell::from(result.duration().map(format_duration).unwrap_or_default()),
+                Cell::from(Span::styled(
+                    result.status.describe().unwrap_or_default(),
+                    Style::default().fg(result.status.color()),
+                )),
+                check,
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(5),
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(18),
+            Constraint::Length(6),
+        ];
+
+        let mut title = format!("Run all ({}/{})", self.results.len(), self.total);
+        match self.build_time {
+            Some(build_time) => {
+                title.push_str(&format!(", built in {}", format_duration(build_time)))
+            }
+            None => title.push_str(", building"),
+        }
+        if let Some(elapsed) = self.elapsed {
+            title.push_str(&format!(", finished in {}", format_duration(elapsed)));
+        }
+        if let Some(exported) = &self.exported {
+            title.push_str(&format!(", saved to {}", exported));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(title)
+            .title_bottom(format!(
+                "{}, {}, {}",
+                keys.describe(&[Action::ExportMarkdown], "Save Markdown"),
+                keys.describe(&[Action::ExportCsv], "Save CSV"),
+                keys.describe(&[Action::RunAll, Action::Close], "Close")
+            ));
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+}
+
+/// Builds and runs a task to the end and collects its result. `input_file`
+/// is relative to the task directory, as for `taskrunner::spawn_task`.
+pub async fn run_task(day: u8, part: u8, input_file: Option<&str>) -> TaskResult {
+    let task_path = taskfinder::task_path(day, part);
+    let handle = taskrunner::spawn_task(&task_path, input_file).map_err(|e| e.to_string());
+    collect_run(day, part, input_file, handle).await
+}
+
+/// Runs a task built by `taskrunner::build_tasks` with its default input, or
+/// records that it failed to build if it has no executable.
+async fn run_built(day: u8, part: u8, executable: Option<Executable>) -> TaskResult {
+    let Some(executable) = executable else {
+        let mut result = TaskResult::new(day, part, None);
+        result.status = TaskStatus::BuildFailed;
+        return result;
+    };
+    let task_path = taskfinder::task_path(day, part);
+    let handle = taskrunner::spawn_built(&task_path, None, executable).map_err(|e| e.to_string());
+    collect_run(day, part, None, handle).await
+}
+
+/// Collects a task's output until it ends and checks its answer.
+async fn collect_run(
+    day: u8,
+    part: u8,
+    input_file: Option<&str>,
+    handle: Result<TaskHandle, String>,
+) -> TaskResult {
+    let task_path = taskfinder::task_path(day, part);
+    let mut result = TaskResult::new(day, part, input_file);
+    match handle {
+        Ok(mut handle) => {
+            while let Some(output) = handle.output.recv().await {
+                if result.collect(output) {
+                    break;
+                }
+            }
+        }
+        Err(e) => result.stderr.push(format!("Failed to run task: {}", e)),
+    }
+
+    result.verdict = Answers::load(&task_path)
+        .verify(&answers::input_name(input_file), result.answer.as_deref());
+    result
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_with(results: Vec<TaskResult>) -> BatchRun {
+        let mut batch = BatchRun::start(Vec::new(), 1);
+        batch.total = results.len();
+        for result in results {
+            batch.add_result(result);
+        }
+        batch
+    }
+
+    fn result(day: u8, part: u8, answer: &str) -> TaskResult {
+        TaskResult {
+            day,
+            part,
+            input_file: None,
+            status: TaskStatus::Solved,
+            stats: RunStats {
+                parse_time: Some(Duration::from_millis(1)),
+                run_time: Some(Duration::from_millis(2)),
+                ..RunStats::default()
+            },
+            answer: Some(answer.to_string()),
+            verdict: Some(Verdict::Correct),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_results_are_ordered() {
+        let batch = batch_with(vec![
+            result(2, 1, "1"),
+            result(1, 2, "2"),
+            result(1, 1, "3"),
+        ]);
+        let order: Vec<(u8, u8)> = batch.results.iter().map(|r| (r.day, r.part)).collect();
+        assert_eq!(order, vec![(1, 1), (1, 2), (2, 1)]);
+        assert!(batch.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_to_markdown() {
+        let batch = batch_with(vec![result(1, 1, "11")]);
+        assert_eq!(
+            batch.to_markdown(),
+            "| Day | Part | Answer | Duration | Status | Check |\n\
+             | ---: | ---: | --- | ---: | --- | :---: |\n\
+             | 1 | 1 | 11 | 3ms | solved | ✓ |\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_to_csv_quotes_answers() {
+        let batch = batch_with(vec![result(17, 1, "4,6,3")]);
+        assert_eq!(
+            batch.to_csv(),
+            "day,part,answer,duration_ms,status,check\n17,1,\"4,6,3\",3.000,solved,correct\n"
+        );
+    }
+}
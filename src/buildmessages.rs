@@ -0,0 +1,145 @@
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// A message from `cargo build --message-format=json`, reduced to the parts
+/// the launcher displays.
+#[derive(Debug, PartialEq)]
+pub enum BuildMessage {
+    /// A crate finished compiling, or was already up to date if `fresh`.
+    Artifact {
+        name: String,
+        executable: Option<PathBuf>,
+        fresh: bool,
+    },
+    Diagnostic(Diagnostic),
+    Finished {
+        success: bool,
+    },
+}
+
+/// A compiler warning or error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub level: String,
+    pub message: String,
+    /// The primary span as `file:line:column`.
+    pub location: Option<String>,
+    /// The diagnostic as rustc would print it, including ANSI colours.
+    pub rendered: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level.starts_with("error")
+    }
+
+    /// A one line summary, e.g. "error: mismatched types at src/main.rs:1:26".
+    pub fn summary(&self) -> String {
+        match &self.location {
+            Some(location) => format!("{}: {} at {}", self.level, self.message, location),
+            None => format!("{}: {}", self.level, self.message),
+        }
+    }
+}
+
+/// Parses one line of cargo's JSON output. Lines that are not JSON, and
+/// messages the launcher has no use for, return `None`.
+pub fn parse_line(line: &str) -> Option<BuildMessage> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    match value["reason"].as_str()? {
+        "compiler-artifact" => Some(BuildMessage::Artifact {
+            name: value["target"]["name"].as_str()?.to_string(),
+            executable: value["executable"].as_str().map(PathBuf::from),
+            fresh: value["fresh"].as_bool().unwrap_or(false),
+        }),
+        "compiler-message" => parse_diagnostic(&value["message"]).map(BuildMessage::Diagnostic),
+        "build-finished" => Some(BuildMessage::Finished {
+            success: value["success"].as_bool().unwrap_or(false),
+        }),
+        _ => None,
+    }
+}
+
+fn parse_diagnostic(message: &Value) -> Option<Diagnostic> {
+    let location = message["spans"]
+        .as_array()
+        .and_then(|spans| {
+            spans
+                .iter()
+                .find(|span| span["is_primary"].as_bool() == Some(true))
+        })
+        .map(|span| {
+            format!(
+                "{}:{}:{}",
+                span["file_name"].as_str().unwrap_or("?"),
+                span["line_start"],
+                span["column_start"]
+            )
+        });
+
+    Some(Diagnostic {
+        level: message["level"].as_str()?.to_string(),
+        message: message["message"].as_str()?.to_string(),
+        location,
+        rendered: message["rendered"].as_str().map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_artifact() {
+        let line = r#"{"reason":"compiler-artifact","package_id":"path+file:///root/crate/day1/task1#0.1.0","target":{"kind":["bin"],"name":"task1"},"executable":"/root/crate/day1/task1/target/debug/task1","fresh":true}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(BuildMessage::Artifact {
+                name: "task1".to_string(),
+                executable: Some(PathBuf::from("/root/crate/day1/task1/target/debug/task1")),
+                fresh: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_library_artifact() {
+        let line = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"itertools"},"executable":null,"fresh":false}"#;
+        assert_eq!(
+            parse_line(line),
+            Some(BuildMessage::Artifact {
+                name: "itertools".to_string(),
+                executable: None,
+                fresh: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostic() {
+        let line = r#"{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","spans":[{"file_name":"src/main.rs","line_start":1,"column_start":20,"is_primary":false},{"file_name":"src/main.rs","line_start":1,"column_start":26,"is_primary":true}],"rendered":"error[E0308]: mismatched types\n"}}"#;
+        let diagnostic = match parse_line(line) {
+            Some(BuildMessage::Diagnostic(diagnostic)) => diagnostic,
+            other => panic!("expected a diagnostic, got {:?}", other),
+        };
+        assert!(diagnostic.is_error());
+        assert_eq!(
+            diagnostic.summary(),
+            "error: mismatched types at src/main.rs:1:26"
+        );
+    }
+
+    #[test]
+    fn test_parse_build_finished() {
+        assert_eq!(
+            parse_line(r#"{"reason":"build-finished","success":false}"#),
+            Some(BuildMessage::Finished { success: false })
+        );
+    }
+
+    #[test]
+    fn test_ignores_other_lines() {
+        assert_eq!(parse_line(r#"{"reason":"build-script-executed"}"#), None);
+        assert_eq!(parse_line("not json"), None);
+    }
+}
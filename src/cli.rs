@@ -0,0 +1,347 @@
+use crate::answers::Verdict;
+use crate::batch::{self, BatchRun, ExportFormat, TaskResult};
+use crate::config::Config;
+use crate::taskfinder;
+use crate::taskpreview::TaskStatus;
+use crate::taskrunner::{self, format_duration, RunStats, TaskOutput};
+
+use serde_json::{json, Value};
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage: advent-of-code-2024 [command] [--json]
+
+Commands:
+  list                         List every task and its input files
+  run <day> <task> [input]     Run a task, optionally with a file from its
+                               input directory instead of input.txt
+  run-all [--jobs N] [--csv]   Run every task with its default input, N at
+                               a time, and print a Markdown or CSV summary
+                               table. N defaults to `jobs` under [run] in
+                               the config, or else the number of CPUs
+  test <day>                   Run cargo test for each task of a day
+
+Without a command the task launcher is opened. --json prints the results as
+JSON instead of plain text. Exits with 1 if a task fails or gives the wrong
+answer, and with 2 on a usage error.";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Plain,
+    Csv,
+    Json,
+}
+
+/// Runs a command without the TUI and returns the process exit code, or the
+/// message to print if the arguments are not understood.
+pub async fn run(args: &[String]) -> Result<i32, String> {
+    let mut format = Format::Plain;
+    let mut jobs = None;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => format = Format::Json,
+            "--csv" => format = Format::Csv,
+            "--jobs" => {
+                jobs = Some(
+                    args.next()
+                        .and_then(|jobs| jobs.parse().ok())
+                        .filter(|&jobs| jobs > 0)
+                        .ok_or_else(|| "--jobs expects a positive number".to_string())?,
+                );
+            }
+            // The flag that ran every task before there were commands.
+            "--run-all" => positional.push("run-all"),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(0);
+            }
+            flag if flag.starts_with("--") => return Err(USAGE.to_string()),
+            command => positional.push(command),
+        }
+    }
+
+    match positional.as_slice() {
+        ["list"] => Ok(list(format).await),
+        ["run", day, task] => Ok(run_task(parse_day(day)?, parse_part(task)?, None, format).await),
+        ["run", day, task, input] => {
+            let (day, part) = (parse_day(day)?, parse_part(task)?);
+            let input_file = format!("input/{}", input);
+            Ok(run_task(day, part, Some(&input_file), format).await)
+        }
+        ["run-all"] => {
+            let jobs = match jobs {
+                Some(jobs) => jobs,
+                None => Config::load()?.jobs,
+            };
+            Ok(run_all(jobs, format).await)
+        }
+        ["test", day] => test(parse_day(day)?, format).await,
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.trim_start_matches("day")
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", day))
+}
+
+/// Parses a task, accepting both `2` and `task2`.
+fn parse_part(task: &str) -> Result<u8, String> {
+    match task.trim_start_matches("task").parse() {
+        Ok(part @ (1 | 2)) => Ok(part),
+        _ => Err(format!("Invalid task: {}, expected 1 or 2", task)),
+    }
+}
+
+/// Lists every task, building the `aoc` runner if needed to find out which
+/// are registered in it.
+async fn list(format: Format) -> i32 {
+    let Some(solutions) = taskrunner::load_solutions().await else {
+        eprintln!("Failed to build the aoc runner, run `cargo build --package aoc` for the errors");
+        return 1;
+    };
+    let tasks = taskfinder::all_tasks().into_iter().map(|(day, part)| {
+        let task_path = taskfinder::task_path(day, part);
+        let inputs = taskfinder::input_files(&task_path);
+        let registered = solutions.contains(&(day, part));
+        (day, part, task_path, inputs, registered)
+    });
+
+    if format == Format::Json {
+        let tasks: Vec<Value> = tasks
+            .map(|(day, part, task_path, inputs, registered)| {
+                json!({
+                    "day": day,
+                    "part": part,
+                    "path": task_path,
+                    "registered": registered,
+                    "inputs": inputs,
+                })
+            })
+            .collect();
+        println!("{}", Value::Array(tasks));
+    } else {
+        for (_, _, task_path, inputs, registered) in tasks {
+            let kind = if registered { "registered" } else { "cargo" };
+            let line = format!("{:<12} {:<10} {}", task_path, kind, inputs.join(", "));
+            println!("{}", line.trim_end());
+        }
+    }
+    0
+}
+
+async fn run_task(day: u8, part: u8, input_file: Option<&str>, format: Format) -> i32 {
+    let result = batch::run_task(day, part, input_file).await;
+
+    if format == Format::Json {
+        println!("{}", result_json(&result, true));
+    } else {
+        result.stdout.iter().for_each(|line| println!("{}", line));
+        result.stderr.iter().for_each(|line| eprintln!("{}", line));
+        println!("{}", summary(&result));
+    }
+    exit_code([&result])
+}
+
+async fn run_all(jobs: usize, format: Format) -> i32 {
+    let mut batch = BatchRun::start(taskfinder::all_tasks(), jobs);
+    batch.wait().await;
+
+    match format {
+        Format::Plain => print!("{}", batch.format(ExportFormat::Markdown)),
+        Format::Csv => print!("{}", batch.format(ExportFormat::Csv)),
+        Format::Json => {
+            let results: Vec<Value> = batch
+                .results
+                .iter()
+                .map(|result| result_json(result, false))
+                .collect();
+            println!("{}", Value::Array(results));
+        }
+    }
+    exit_code(&batch.results)
+}
+
+/// Runs `cargo test` for each task of the day in turn. Plain output is
+/// passed through as it arrives, followed by a line per task.
+async fn test(day: u8, format: Format) -> Result<i32, String> {
+    let tasks: Vec<String> = taskfinder::all_tasks()
+        .into_iter()
+        .filter(|(task_day, _)| *task_day == day)
+        .map(|(day, part)| taskfinder::task_path(day, part))
+        .collect();
+    if tasks.is_empty() {
+        return Err(format!("Day {} has no tasks", day));
+    }
+
+    let mut results = Vec::new();
+    for task_path in &tasks {
+        let mut handle = taskrunner::spawn_tests(task_path).map_err(|e| e.to_string())?;
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut status = TaskStatus::Finished(None);
+        while let Some(output) = handle.output.recv().await {
+            match output {
+                TaskOutput::Stdout(line) => {
+                    if format == Format::Plain {
+                        println!("{}", line);
+                    }
+                    stdout.push(line);
+                }
+                TaskOutput::Stderr(line) => {
+                    if format == Format::Plain {
+                        eprintln!("{}", line);
+                    }
+                    stderr.push(line);
+                }
+                TaskOutput::Finished(exit_status, _) => {
+                    status = TaskStatus::Finished(exit_status);
+                    break;
+                }
+                TaskOutput::Cancelled(_) => {
+                    status = TaskStatus::Cancelled;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        results.push((task_path, status, stdout, stderr));
+    }
+
+    if format == Format::Json {
+        let results: Vec<Value> = results
+            .iter()
+            .map(|(task_path, status, stdout, stderr)| {
+                json!({
+                    "task": task_path,
+                    "success": status.succeeded(),
+                    "status": status.describe(),
+                    "stdout": stdout,
+                    "stderr": stderr,
+                })
+            })
+            .collect();
+        println!("{}", Value::Array(results));
+    } else {
+        for (task_path, status, _, _) in &results {
+            let outcome = if status.succeeded() { "ok" } else { "FAILED" };
+            let status = status.describe().unwrap_or_default();
+            println!("{}: {} ({})", task_path, outcome, status);
+        }
+    }
+    let passed = results.iter().all(|(_, status, _, _)| status.succeeded());
+    Ok(if passed { 0 } else { 1 })
+}
+
+/// Summarises a run on one line, e.g.
+/// `day1/task1: 11 (solved, parse 52µs, run 8µs) ✓`.
+fn summary(result: &TaskResult) -> String {
+    let mut details = vec![result.status.describe().unwrap_or_default()];
+    let stats = result.stats.summary();
+    if !stats.is_empty() {
+        details.push(stats);
+    }
+    let mut summary = format!(
+        "{}: {} ({})",
+        taskfinder::task_path(result.day, result.part),
+        result.answer.as_deref().unwrap_or("no answer"),
+        details.join(", ")
+    );
+    match &result.verdict {
+        Some(Verdict::Correct) => summary.push_str(" ✓"),
+        Some(Verdict::Wrong { expected }) => summary.push_str(&format!(" ✗ expected {}", expected)),
+        None => {}
+    }
+    summary
+}
+
+fn result_json(result: &TaskResult, include_output: bool) -> Value {
+    let milliseconds =
+        |duration: Option<Duration>| duration.map(|duration| duration.as_secs_f64() * 1000.0);
+    let RunStats {
+        build_time,
+        parse_time,
+        run_time,
+        peak_rss_kb,
+    } = result.stats;
+    let (check, expected) = match &result.verdict {
+        Some(Verdict::Correct) => (Some("correct"), None),
+        Some(Verdict::Wrong { expected }) => (Some("wrong"), Some(expected.as_str())),
+        None => (None, None),
+    };
+
+    let mut value = json!({
+        "day": result.day,
+        "part": result.part,
+        "input": result.input_file,
+        "status": result.status.describe(),
+        "success": result.status.succeeded(),
+        "answer": result.answer,
+        "check": check,
+        "expected": expected,
+        "duration": result.duration().map(format_duration),
+        "build_ms": milliseconds(build_time),
+        "parse_ms": milliseconds(parse_time),
+        "run_ms": milliseconds(run_time),
+        "peak_rss_kb": peak_rss_kb,
+    });
+    if include_output {
+        value["stdout"] = json!(result.stdout);
+        value["stderr"] = json!(result.stderr);
+    }
+    value
+}
+
+/// Fails if any task failed or gave the wrong answer.
+fn exit_code<'a>(results: impl IntoIterator<Item = &'a TaskResult>) -> i32 {
+    let passed = results.into_iter().all(|result| {
+        result.status.succeeded() && !matches!(result.verdict, Some(Verdict::Wrong { .. }))
+    });
+    if passed {
+        0
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day_and_part() {
+        assert_eq!(parse_day("12"), Ok(12));
+        assert_eq!(parse_day("day3"), Ok(3));
+        assert!(parse_day("x").is_err());
+        assert_eq!(parse_part("task2"), Ok(2));
+        assert_eq!(parse_part("1"), Ok(1));
+        assert!(parse_part("3").is_err());
+    }
+
+    #[test]
+    fn test_summary() {
+        let result = TaskResult {
+            day: 1,
+            part: 1,
+            input_file: None,
+            status: TaskStatus::Solved,
+            stats: RunStats {
+                parse_time: Some(Duration::from_micros(52)),
+                ..RunStats::default()
+            },
+            answer: Some("11".to_string()),
+            verdict: Some(Verdict::Wrong {
+                expected: "12".to_string(),
+            }),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        };
+        assert_eq!(
+            summary(&result),
+            "day1/task1: 11 (solved, parse 52µs) ✗ expected 12"
+        );
+        assert_eq!(exit_code([&result]), 1);
+    }
+}
@@ -0,0 +1,635 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::Color;
+use serde::de::{self, Error as _, IntoDeserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The config file looked for in the project directory. It overrides the
+/// user's config file, which is looked for under `$XDG_CONFIG_HOME`.
+pub const PROJECT_CONFIG_FILE: &str = "aoc.toml";
+
+/// Something the launcher can be asked to do from the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    /// Closes the open popup or search, or quits if there is none.
+    Close,
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Searches the output when the preview shows a task's output, and the
+    /// task tree otherwise.
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Run,
+    Test,
+    /// Selects the next test or run where there is a list, and otherwise
+    /// scrolls the preview.
+    ScrollDown,
+    ScrollUp,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    ScrollLeft,
+    ScrollRight,
+    Wrap,
+    /// Expands the selected test or marks the selected run.
+    Toggle,
+    Compare,
+    Edit,
+    Cancel,
+    Colours,
+    NextTab,
+    History,
+    RunAll,
+    ExportMarkdown,
+    ExportCsv,
+    Palette,
+    ToggleHeader,
+    ToggleControls,
+    ShrinkFinder,
+    GrowFinder,
+}
+
+impl Action {
+    pub const ALL: [Action; 35] = [
+        Action::Quit,
+        Action::Close,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Search,
+        Action::NextMatch,
+        Action::PreviousMatch,
+        Action::Run,
+        Action::Test,
+        Action::ScrollDown,
+        Action::ScrollUp,
+        Action::PageDown,
+        Action::PageUp,
+        Action::Top,
+        Action::Bottom,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::Wrap,
+        Action::Toggle,
+        Action::Compare,
+        Action::Edit,
+        Action::Cancel,
+        Action::Colours,
+        Action::NextTab,
+        Action::History,
+        Action::RunAll,
+        Action::ExportMarkdown,
+        Action::ExportCsv,
+        Action::Palette,
+        Action::ToggleHeader,
+        Action::ToggleControls,
+        Action::ShrinkFinder,
+        Action::GrowFinder,
+    ];
+
+    /// The action's name in the config file, e.g. `next_tab`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Close => "close",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Search => "search",
+            Action::NextMatch => "next_match",
+            Action::PreviousMatch => "previous_match",
+            Action::Run => "run",
+            Action::Test => "test",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollUp => "scroll_up",
+            Action::PageDown => "page_down",
+            Action::PageUp => "page_up",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::ScrollLeft => "scroll_left",
+            Action::ScrollRight => "scroll_right",
+            Action::Wrap => "wrap",
+            Action::Toggle => "toggle",
+            Action::Compare => "compare",
+            Action::Edit => "edit",
+            Action::Cancel => "cancel",
+            Action::Colours => "colours",
+            Action::NextTab => "next_tab",
+            Action::History => "history",
+            Action::RunAll => "run_all",
+            Action::ExportMarkdown => "export_markdown",
+            Action::ExportCsv => "export_csv",
+            Action::Palette => "palette",
+            Action::ToggleHeader => "toggle_header",
+            Action::ToggleControls => "toggle_controls",
+            Action::ShrinkFinder => "shrink_finder",
+            Action::GrowFinder => "grow_finder",
+        }
+    }
+
+    /// Says what the action does, for the command palette.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Close => "Close the popup or search",
+            Action::Up => "Select the item above in the tree",
+            Action::Down => "Select the item below in the tree",
+            Action::Left => "Close the day or task in the tree",
+            Action::Right => "Open the day or task in the tree",
+            Action::Search => "Search the output shown, or else the tasks",
+            Action::NextMatch => "Go to the next match in the output",
+            Action::PreviousMatch => "Go to the previous match in the output",
+            Action::Run => "Run the selected task or input",
+            Action::Test => "Test the selected task or day",
+            Action::ScrollDown => "Scroll down, or select the next test or run",
+            Action::ScrollUp => "Scroll up, or select the previous test or run",
+            Action::PageDown => "Scroll down a page",
+            Action::PageUp => "Scroll up a page",
+            Action::Top => "Scroll to the top",
+            Action::Bottom => "Scroll to the bottom",
+            Action::ScrollLeft => "Scroll left",
+            Action::ScrollRight => "Scroll right",
+            Action::Wrap => "Wrap long lines or scroll them sideways",
+            Action::Toggle => "Expand the selected test or mark the selected run",
+            Action::Compare => "Compare the marked run with the selected one",
+            Action::Edit => "Open the task's source in $EDITOR",
+            Action::Cancel => "Kill the running task",
+            Action::Colours => "Show output with or without colours",
+            Action::NextTab => "Switch to the next preview tab",
+            Action::History => "Show the run history of the task",
+            Action::RunAll => "Run every task",
+            Action::ExportMarkdown => "Export the results of every task as Markdown",
+            Action::ExportCsv => "Export the results of every task as CSV",
+            Action::Palette => "Open the command palette",
+            Action::ToggleHeader => "Show or hide the banner",
+            Action::ToggleControls => "Show or hide the list of controls",
+            Action::ShrinkFinder => "Make the task tree narrower",
+            Action::GrowFinder => "Make the task tree wider",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Close => &["esc"],
+            Action::Up => &["w", "up"],
+            Action::Down => &["s", "down"],
+            Action::Left => &["a", "left"],
+            Action::Right => &["d", "right"],
+            Action::Search => &["/"],
+            Action::NextMatch => &["n"],
+            Action::PreviousMatch => &["N"],
+            Action::Run => &["enter"],
+            Action::Test => &["t"],
+            Action::ScrollDown => &["j"],
+            Action::ScrollUp => &["k"],
+            Action::PageDown => &["pgdn"],
+            Action::PageUp => &["pgup"],
+            Action::Top => &["home"],
+            Action::Bottom => &["end"],
+            Action::ScrollLeft => &["<"],
+            Action::ScrollRight => &[">"],
+            Action::Wrap => &["z"],
+            Action::Toggle => &["space"],
+            Action::Compare => &["C"],
+            Action::Edit => &["e"],
+            Action::Cancel => &["x"],
+            Action::Colours => &["c"],
+            Action::NextTab => &["tab"],
+            Action::History => &["h"],
+            Action::RunAll => &["r"],
+            Action::ExportMarkdown => &["m"],
+            Action::ExportCsv => &["v"],
+            Action::Palette => &[":", "ctrl-p"],
+            Action::ToggleHeader => &["H"],
+            Action::ToggleControls => &["?"],
+            Action::ShrinkFinder => &["["],
+            Action::GrowFinder => &["]"],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// A key press as bindings see it. Shift is part of the character, so only
+/// Ctrl is tracked as a modifier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+}
+
+impl Key {
+    pub fn from_event(event: KeyEvent) -> Key {
+        Key {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        }
+    }
+
+    /// Reads a key as written in the config file: a single character such as
+    /// `q` or `N`, a named key such as `enter` or `pgdn`, or either with a
+    /// `ctrl-` prefix.
+    fn parse(text: &str) -> Option<Key> {
+        let (ctrl, name) = match text.strip_prefix("ctrl-") {
+            Some(name) if !name.is_empty() => (true, name),
+            _ => (false, text),
+        };
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                _ => return None,
+            },
+        };
+        Some(Key { code, ctrl })
+    }
+
+    /// Names the key the way it is written in the config file.
+    pub fn name(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "enter".to_string(),
+            KeyCode::Esc => "esc".to_string(),
+            KeyCode::Tab => "tab".to_string(),
+            KeyCode::Backspace => "backspace".to_string(),
+            KeyCode::Up => "up".to_string(),
+            KeyCode::Down => "down".to_string(),
+            KeyCode::Left => "left".to_string(),
+            KeyCode::Right => "right".to_string(),
+            KeyCode::PageUp => "pgup".to_string(),
+            KeyCode::PageDown => "pgdn".to_string(),
+            KeyCode::Home => "home".to_string(),
+            KeyCode::End => "end".to_string(),
+            code => format!("{:?}", code).to_lowercase(),
+        };
+        if self.ctrl {
+            format!("ctrl-{}", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// The keys bound to each action.
+pub struct KeyBindings {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .filter_map(|key| Key::parse(key))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        KeyBindings { bindings }
+    }
+}
+
+impl KeyBindings {
+    /// The action a key press is bound to, if any.
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys.as_slice())
+    }
+
+    /// Describes some actions for help text by the first key of each, e.g.
+    /// `j/k: Select`. Actions without keys are left out.
+    pub fn describe(&self, actions: &[Action], label: &str) -> String {
+        let keys: Vec<String> = actions
+            .iter()
+            .filter_map(|action| self.keys(*action).first())
+            .map(Key::name)
+            .collect();
+        format!("{}: {}", keys.join("/"), label)
+    }
+
+    fn bind(&mut self, action: Action, keys: Vec<Key>) {
+        if let Some((_, bound)) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            *bound = keys;
+        }
+    }
+
+    /// Makes sure that no key is bound to two actions.
+    fn check_conflicts(&self) -> Result<(), String> {
+        for (index, (action, keys)) in self.bindings.iter().enumerate() {
+            for key in keys {
+                if let Some((other, _)) = self.bindings[index + 1..]
+                    .iter()
+                    .find(|(_, other_keys)| other_keys.contains(key))
+                {
+                    return Err(format!(
+                        "The key `{}` is bound to both {} and {}",
+                        key.name(),
+                        action.name(),
+                        other.name()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The colours the launcher is drawn in, apart from those that mean
+/// something such as a task's status.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    /// The colour of the preview, and of every other letter of the banner and
+    /// line of the controls.
+    pub primary: Color,
+    /// The colour of the task tree, and of the rest of the banner and
+    /// controls.
+    pub secondary: Color,
+    /// The colour of the selected task.
+    pub highlight: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            primary: Color::Green,
+            secondary: Color::Red,
+            highlight: Color::Green,
+        }
+    }
+}
+
+pub struct Config {
+    pub keys: KeyBindings,
+    pub theme: Theme,
+    /// How many tasks running every task runs at a time.
+    pub jobs: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            keys: KeyBindings::default(),
+            theme: Theme::default(),
+            jobs: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the user's config file and then the project's, each overriding
+    /// what came before. Missing files are fine, but files that cannot be
+    /// understood are reported with the line at fault.
+    pub fn load() -> Result<Config, String> {
+        let mut config = Config::default();
+        for path in config_paths() {
+            match std::fs::read_to_string(&path) {
+                Ok(source) => config.apply(&path.display().to_string(), &source)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+            }
+        }
+        config.keys.check_conflicts()?;
+        Ok(config)
+    }
+
+    fn apply(&mut self, file: &str, source: &str) -> Result<(), String> {
+        let file_config: ConfigFile =
+            toml::from_str(source).map_err(|e| format!("{}: {}", file, e))?;
+        for (action, keys) in file_config.keys {
+            self.keys.bind(action, keys.0);
+        }
+        let theme = file_config.theme;
+        self.theme.primary = theme.primary.unwrap_or(self.theme.primary);
+        self.theme.secondary = theme.secondary.unwrap_or(self.theme.secondary);
+        self.theme.highlight = theme.highlight.unwrap_or(self.theme.highlight);
+        if let Some(jobs) = file_config.run.jobs {
+            self.jobs = jobs.get();
+        }
+        Ok(())
+    }
+}
+
+/// The config files to read, in order: the user's and then the project's.
+fn config_paths() -> Vec<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    let mut paths: Vec<PathBuf> = config_home
+        .map(|dir| dir.join("advent-of-code-2024").join("config.toml"))
+        .into_iter()
+        .collect();
+    paths.push(PathBuf::from(PROJECT_CONFIG_FILE));
+    paths
+}
+
+/// A config file as written. Everything in it is optional, and only what is
+/// set overrides the files read before it.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    keys: HashMap<Action, KeyList>,
+    #[serde(default)]
+    theme: ThemeFile,
+    #[serde(default)]
+    run: RunFile,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunFile {
+    jobs: Option<NonZeroUsize>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    #[serde(default, deserialize_with = "colour")]
+    primary: Option<Color>,
+    #[serde(default, deserialize_with = "colour")]
+    secondary: Option<Color>,
+    #[serde(default, deserialize_with = "colour")]
+    highlight: Option<Color>,
+}
+
+/// Reads a colour by name, e.g. `blue`, or as `#rrggbb`.
+fn colour<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Color::from_str(&name)
+        .map(Some)
+        .map_err(|_| D::Error::custom(format!("unknown colour `{}`", name)))
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Action, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Action::from_name(&name).ok_or_else(|| {
+            let names: Vec<&str> = Action::ALL.iter().map(|a| a.name()).collect();
+            D::Error::custom(format!(
+                "unknown action `{}`, expected one of: {}",
+                name,
+                names.join(", ")
+            ))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Key, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Key::parse(&name).ok_or_else(|| D::Error::custom(format!("unknown key `{}`", name)))
+    }
+}
+
+/// The keys bound to an action, written as one key or as an array of them.
+struct KeyList(Vec<Key>);
+
+impl<'de> Deserialize<'de> for KeyList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<KeyList, D::Error> {
+        struct KeyListVisitor;
+
+        impl<'de> Visitor<'de> for KeyListVisitor {
+            type Value = KeyList;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a key or an array of keys")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<KeyList, E> {
+                Key::deserialize(name.into_deserializer()).map(|key| KeyList(vec![key]))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KeyList, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(key) = seq.next_element()? {
+                    keys.push(key);
+                }
+                Ok(KeyList(keys))
+            }
+        }
+
+        deserializer.deserialize_any(KeyListVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let mut config = Config::default();
+        let source = r##"
+            # Vim-style movement.
+            [keys]
+            up = ["k", "up"] # and the arrow
+            down = ['j', "down"]
+            scroll_up = "ctrl-u"
+            scroll_down = "ctrl-d"
+            "run" = "space"
+            toggle = "enter"
+
+            [theme]
+            primary = "#ff8800"
+            secondary = "blue"
+
+            [run]
+            jobs = 3
+        "##;
+        config.apply("aoc.toml", source).unwrap();
+        assert!(config.keys.check_conflicts().is_ok());
+
+        let key = |code| Key { code, ctrl: false };
+        assert_eq!(
+            config.keys.action(key(KeyCode::Char('k'))),
+            Some(Action::Up)
+        );
+        assert_eq!(config.keys.action(key(KeyCode::Char('w'))), None);
+        assert_eq!(
+            config.keys.action(key(KeyCode::Char(' '))),
+            Some(Action::Run)
+        );
+        assert_eq!(
+            config.keys.action(Key {
+                code: KeyCode::Char('u'),
+                ctrl: true
+            }),
+            Some(Action::ScrollUp)
+        );
+        assert_eq!(
+            config
+                .keys
+                .describe(&[Action::Up, Action::Down], "Up, down"),
+            "k/j: Up, down"
+        );
+        assert_eq!(config.theme.primary, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(config.theme.secondary, Color::Blue);
+        assert_eq!(config.jobs, 3);
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let error = |source: &str| {
+            let mut config = Config::default();
+            config
+                .apply("aoc.toml", source)
+                .and_then(|_| config.keys.check_conflicts())
+                .unwrap_err()
+        };
+        let fly = error("[keys]\nfly = \"f\"");
+        assert!(fly.starts_with("aoc.toml: TOML parse error at line 2"));
+        assert!(fly.contains("unknown action `fly`, expected one of: quit, close"));
+        assert!(error("[keys]\nquit = \"ctrl-\"x").contains("line 2"));
+        let hyper = error("[keys]\n\nquit = [\"q\", \"hyper-q\"]");
+        assert!(hyper.contains("line 3"));
+        assert!(hyper.contains("unknown key `hyper-q`"));
+        assert!(error("[theme]\nprimary = \"greenish\"").contains("unknown colour `greenish`"));
+        assert!(error("[keys]\nquit = 3").contains("expected a key or an array of keys"));
+        assert!(error("quit = \"q\"").contains("unknown field `quit`"));
+        assert!(error("[run]\njobs = 0").contains("line 2"));
+        assert_eq!(
+            error("[keys]\nquit = \"w\""),
+            "The key `w` is bound to both quit and up"
+        );
+    }
+}
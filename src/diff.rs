@@ -0,0 +1,146 @@
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use similar::{Algorithm, DiffTag};
+use std::time::{Duration, Instant};
+
+/// How many unchanged lines are shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// How long a comparison may take before it settles for a longer diff.
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Compares two texts line by line with Myers' algorithm, so that the changes
+/// are as small as possible. Returns every line of both texts in order with
+/// whether it was removed, added or kept.
+///
+/// The comparison runs on the UI thread, so texts that differ too much to
+/// be compared within `TIMEOUT` get a diff that is correct but may be
+/// longer than it needs to be.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(Change, &'a str)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let deadline = Instant::now() + TIMEOUT;
+
+    let mut lines = Vec::new();
+    for op in similar::capture_diff_slices_deadline(Algorithm::Myers, &old, &new, Some(deadline)) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        let removed = old[old_range].iter().map(|line| (Change::Removed, *line));
+        let added = new[new_range].iter().map(|line| (Change::Added, *line));
+        match tag {
+            DiffTag::Equal => lines.extend(removed.map(|(_, line)| (Change::Same, line))),
+            DiffTag::Delete | DiffTag::Insert | DiffTag::Replace => {
+                lines.extend(removed.chain(added))
+            }
+        }
+    }
+    lines
+}
+
+/// Renders a diff in the unified format, with `@@ -a,b +c,d @@` headers for
+/// each hunk of changes and `CONTEXT_LINES` of context around them.
+pub fn unified(diff: &[(Change, &str)]) -> Vec<Line<'static>> {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, (change, _))| *change != Change::Same)
+        .map(|(index, _)| index)
+        .collect();
+    if changed.is_empty() {
+        return vec![Line::from("The files are identical")];
+    }
+
+    // Group the changes into hunks whose context would overlap.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some((_, hunk_end)) if start <= *hunk_end => *hunk_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (start, end) in hunks {
+        let old_lines =
+            |range: &[(Change, &str)]| range.iter().filter(|(c, _)| *c != Change::Added).count();
+        let new_lines =
+            |range: &[(Change, &str)]| range.iter().filter(|(c, _)| *c != Change::Removed).count();
+        lines.push(Line::styled(
+            format!(
+                "@@ -{},{} +{},{} @@",
+                old_lines(&diff[..start]) + 1,
+                old_lines(&diff[start..end]),
+                new_lines(&diff[..start]) + 1,
+                new_lines(&diff[start..end])
+            ),
+            Style::default().fg(Color::Cyan),
+        ));
+        lines.extend(diff[start..end].iter().map(|(change, line)| match change {
+            Change::Same => Line::from(format!(" {}", line)),
+            Change::Removed => Line::styled(format!("-{}", line), Color::Red),
+            Change::Added => Line::styled(format!("+{}", line), Color::Green),
+        }));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                (Change::Same, "a"),
+                (Change::Removed, "b"),
+                (Change::Same, "c"),
+                (Change::Added, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_of_long_outputs() {
+        let old: String = (0..5000).map(|i| format!("{}\r\n", i)).collect();
+        let new: String = (0..5000).map(|i| format!("{}\n", i * 2)).collect();
+        let diff = diff_lines(&old, &new);
+        let side = |skip: Change| -> Vec<&str> {
+            diff.iter()
+                .filter(|(change, _)| *change != skip)
+                .map(|(_, line)| *line)
+                .collect()
+        };
+        assert_eq!(side(Change::Added), old.lines().collect::<Vec<_>>());
+        assert_eq!(side(Change::Removed), new.lines().collect::<Vec<_>>());
+        assert_eq!(diff[0], (Change::Same, "0"));
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\n5\n6\n7\n8\nnine\n10\n";
+        let lines: Vec<String> = unified(&diff_lines(old, new))
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            vec!["@@ -6,5 +6,5 @@", " 6", " 7", " 8", "-9", "+nine", " 10"]
+        );
+        assert_eq!(
+            unified(&diff_lines(old, old))[0].to_string(),
+            "The files are identical"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+/// Scores how well `query` matches `candidate` as a case-insensitive
+/// subsequence, or returns `None` if it does not match. Runs of consecutive
+/// characters and matches at the start of a word or number score higher, so
+/// `12` prefers `day12` over `day1/task2`, and shorter candidates win ties.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match = None;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let offset = candidate[position..]
+            .iter()
+            .position(|c| c.to_lowercase().eq(query_char.to_lowercase()))?;
+        let index = position + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 16;
+        } else if is_word_start(&candidate, index) {
+            score += 8;
+        }
+        if let Some(previous) = previous_match {
+            score -= (index - previous - 1) as i64;
+        }
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score * 100 - candidate.len() as i64)
+}
+
+/// Whether the character at `index` starts a word or a number, e.g. the `t`
+/// and the `2` in `day1/task2`.
+fn is_word_start(candidate: &[char], index: usize) -> bool {
+    let Some(previous) = index.checked_sub(1).map(|i| candidate[i]) else {
+        return true;
+    };
+    let current = candidate[index];
+    !previous.is_alphanumeric() || previous.is_ascii_digit() != current.is_ascii_digit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_matches_subsequences() {
+        assert!(score("d1t2", "day1/task2").is_some());
+        assert!(score("T2", "day1/task2").is_some());
+        assert!(score("", "day1").is_some());
+        assert_eq!(score("t3", "day1/task2"), None);
+        assert_eq!(score("21", "day12"), None);
+    }
+
+    #[test]
+    fn test_score_ranks_better_matches_first() {
+        let score = |query, candidate| score(query, candidate).unwrap();
+        assert!(score("12", "day12") > score("12", "day1/task2"));
+        assert!(score("1", "day1") > score("1", "day10"));
+        assert!(score("d2", "day2/task1") > score("d2", "day1/task2"));
+    }
+}
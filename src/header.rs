@@ -1,15 +1,27 @@
-use ratatui::layout::{Constraint, Direction, Layout};
+use crate::config::{Action, KeyBindings, Theme};
+
+use ratatui::layout::Alignment;
 use ratatui::style::{Color, Style};
-use ratatui::text::Text;
-use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget};
+
+/// The title shown instead of the banner when the terminal is too narrow for
+/// it.
+const TITLE: &str = "Advent of Code 2024";
+
+/// The rows of the banner.
+const BANNER_HEIGHT: u16 = 5;
 
+/// The banner, drawn once from its FIGlet letters when the app starts.
 pub struct Header {
-    letters: Vec<String>,
+    banner: Vec<Line<'static>>,
+    title: Line<'static>,
+    width: u16,
 }
 
 impl Header {
-    pub fn new() -> Header {
-        let letters = vec![
+    pub fn new(theme: Theme) -> Header {
+        let letters = [
             a(),
             d(),
             v(),
@@ -29,85 +41,185 @@ impl Header {
             zero(),
             two(),
             four(),
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+        ];
+        let colour = |i| alternate_colour(theme, i);
 
-        Header { letters }
+        let banner: Vec<Line<'static>> = (0..usize::from(BANNER_HEIGHT))
+            .map(|row| {
+                letters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, letter)| {
+                        let width = letter.lines().next().unwrap().len();
+                        let text = letter.lines().nth(row).unwrap_or_default();
+                        Span::styled(format!("{:<width$}", text), colour(i))
+                    })
+                    .collect()
+            })
+            .collect();
+        let width = banner.first().map_or(0, Line::width) as u16;
+        let title = TITLE
+            .split(' ')
+            .enumerate()
+            .flat_map(|(i, word)| {
+                let separator = if i == 0 { "" } else { " " };
+                [Span::raw(separator), Span::styled(word, colour(i))]
+            })
+            .collect();
+
+        Header {
+            banner,
+            title,
+            width,
+        }
+    }
+
+    /// How many rows the header needs at the given width: the whole banner if
+    /// it fits, or else a one-line title.
+    pub fn height(&self, width: u16) -> u16 {
+        if width >= self.width {
+            BANNER_HEIGHT
+        } else {
+            1
+        }
     }
 }
 
-impl Widget for Header {
+impl Widget for &Header {
     fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                self.letters
-                    .iter()
-                    .map(|s| {
-                        let width = s.lines().next().unwrap().len();
-                        Constraint::Length(width as u16)
-                    })
-                    .collect::<Vec<Constraint>>()
-                    .as_slice(),
-            )
-            .split(area);
-
-        for (i, letter) in self.letters.iter().enumerate() {
-            let color = if i % 2 == 0 { Color::Red } else { Color::Green };
-            let text = Text::styled(letter, Style::default().fg(color));
-            let block = Block::default().borders(ratatui::widgets::Borders::NONE);
-            let paragraph = Paragraph::new(text).block(block);
-            paragraph.render(chunks[i], buf);
+        if self.height(area.width) == BANNER_HEIGHT && area.height >= BANNER_HEIGHT {
+            Paragraph::new(self.banner.clone()).render(area, buf);
+        } else {
+            Paragraph::new(self.title.clone()).render(area, buf);
         }
     }
 }
 
+/// Colours the letters of the banner, the words of the title and the
+/// controls in turn.
+fn alternate_colour(theme: Theme, index: usize) -> Color {
+    if index.is_multiple_of(2) {
+        theme.secondary
+    } else {
+        theme.primary
+    }
+}
+
+/// The help text for the controls, as the actions each line is about and
+/// what they do.
+const HELP: &[(&[Action], &str)] = &[
+    (&[Action::Quit], "Quit"),
+    (&[Action::Up, Action::Down], "Up, down"),
+    (&[Action::Left, Action::Right], "Left, right"),
+    (
+        &[Action::Search, Action::NextMatch, Action::PreviousMatch],
+        "Search tasks or output",
+    ),
+    (
+        &[
+            Action::PageUp,
+            Action::PageDown,
+            Action::ScrollUp,
+            Action::ScrollDown,
+        ],
+        "Scroll",
+    ),
+    (&[Action::Top, Action::Bottom], "Top, bottom"),
+    (
+        &[Action::ScrollLeft, Action::ScrollRight, Action::Wrap],
+        "Scroll sideways, wrap",
+    ),
+    (&[Action::Run], "Run task"),
+    (&[Action::Test], "Test task or day"),
+    (&[Action::Toggle], "Expand test, mark run"),
+    (&[Action::Cancel], "Kill running task"),
+    (&[Action::Colours], "Toggle colours"),
+    (&[Action::NextTab], "Switch output tab"),
+    (&[Action::Edit], "Edit task source"),
+    (&[Action::History, Action::Compare], "Run history, compare"),
+    (&[Action::RunAll], "Run all tasks"),
+    (&[Action::Palette], "Command palette"),
+    (
+        &[Action::ToggleHeader, Action::ToggleControls],
+        "Hide banner, controls",
+    ),
+    (
+        &[Action::ShrinkFinder, Action::GrowFinder],
+        "Resize task tree",
+    ),
+];
+
+/// The help text for the controls, laid out in as few rows as the width
+/// allows.
 pub struct Controls {
     controls: Vec<String>,
+    theme: Theme,
 }
 
+/// The space between two controls on the same row.
+const SEPARATOR: &str = "   ";
+
 impl Controls {
-    pub fn new() -> Controls {
-        let controls = [
-            "q: Quit",
-            "w: Up, s: Down",
-            "a: Left, d: Right",
-            "pgup: Scroll up",
-            "pgdn: Scroll down",
-            "enter: Run task",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        Controls { controls }
-    }
-}
+    /// Builds the help text from the keys that are bound to each action.
+    pub fn new(keys: &KeyBindings, theme: Theme) -> Controls {
+        let controls = HELP
+            .iter()
+            .map(|(actions, label)| keys.describe(actions, label))
+            .collect();
 
-impl Widget for Controls {
-    fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                self.controls
-                    .iter()
-                    .map(|_| Constraint::Length(1))
-                    .collect::<Vec<Constraint>>()
-                    .as_slice(),
-            )
-            .split(area);
+        Controls { controls, theme }
+    }
 
+    /// Packs the controls into rows no wider than `width`, as the indices of
+    /// the controls on each row. A control wider than a row gets one to
+    /// itself.
+    fn rows(&self, width: u16) -> Vec<Vec<usize>> {
+        let width = usize::from(width);
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        let mut row_width = 0;
         for (i, control) in self.controls.iter().enumerate() {
-            let color = if i % 2 == 0 { Color::Red } else { Color::Green };
-            let text = Text::styled(control, Style::default().fg(color));
-            let block = Block::default().borders(ratatui::widgets::Borders::NONE);
-            let paragraph = Paragraph::new(text)
-                .block(block)
-                .alignment(ratatui::layout::Alignment::Right);
-            paragraph.render(chunks[i], buf);
+            let control_width = control.chars().count();
+            match rows.last_mut() {
+                Some(row) if row_width + SEPARATOR.len() + control_width <= width => {
+                    row.push(i);
+                    row_width += SEPARATOR.len() + control_width;
+                }
+                _ => {
+                    rows.push(vec![i]);
+                    row_width = control_width;
+                }
+            }
         }
+        rows
+    }
+
+    pub fn height(&self, width: u16) -> u16 {
+        self.rows(width).len() as u16
+    }
+}
+
+impl Widget for &Controls {
+    fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
+        let lines: Vec<Line> = self
+            .rows(area.width)
+            .into_iter()
+            .map(|row| {
+                let mut spans = Vec::new();
+                for (n, i) in row.into_iter().enumerate() {
+                    if n > 0 {
+                        spans.push(Span::raw(SEPARATOR));
+                    }
+                    spans.push(Span::styled(
+                        self.controls[i].as_str(),
+                        Style::default().fg(alternate_colour(self.theme, i)),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines)
+            .alignment(Alignment::Right)
+            .render(area, buf);
     }
 }
 
@@ -214,3 +326,28 @@ fn space() -> &'static str {
    
    "#
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_collapses_when_narrow() {
+        let header = Header::new(Theme::default());
+        assert_eq!(header.width, 118);
+        assert_eq!(header.height(160), BANNER_HEIGHT);
+        assert_eq!(header.height(117), 1);
+        assert_eq!(header.title.width(), TITLE.len());
+    }
+
+    #[test]
+    fn test_controls_fill_rows() {
+        let controls = Controls {
+            controls: vec!["q: Quit".to_string(), "enter: Run task".to_string()],
+            theme: Theme::default(),
+        };
+        assert_eq!(controls.rows(40), vec![vec![0, 1]]);
+        assert_eq!(controls.rows(20), vec![vec![0], vec![1]]);
+        assert_eq!(controls.rows(5), vec![vec![0], vec![1]]);
+    }
+}
@@ -0,0 +1,314 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// Highlights Rust source code one line at a time. Block comments and string
+/// literals can span lines, so the highlighter carries that state over
+/// between calls to `highlight_line`.
+#[derive(Default)]
+pub struct RustHighlighter {
+    /// How many block comments are open, as they can be nested.
+    comment_depth: usize,
+    /// The text that closes the string literal being read, e.g. `"` or `"#`.
+    string_end: Option<String>,
+}
+
+impl RustHighlighter {
+    pub fn highlight_line(&mut self, line: &str) -> Line<'static> {
+        let mut spans = Vec::new();
+        let mut rest = line;
+
+        if line.trim_start().starts_with("#[") || line.trim_start().starts_with("#![") {
+            return Line::styled(line.to_string(), attribute_style());
+        }
+
+        while !rest.is_empty() {
+            let (len, style) = if self.comment_depth > 0 {
+                (self.block_comment_len(rest), comment_style())
+            } else if let Some(string_end) = self.string_end.clone() {
+                (self.string_len(rest, &string_end), string_style())
+            } else {
+                self.token(rest)
+            };
+            spans.push(Span::styled(rest[..len].to_string(), style));
+            rest = &rest[len..];
+        }
+        Line::from(spans)
+    }
+
+    /// Measures the next token outside of comments and strings, opening a
+    /// comment or string if one starts here.
+    fn token(&mut self, rest: &str) -> (usize, Style) {
+        if rest.starts_with("//") {
+            return (rest.len(), comment_style());
+        }
+        if let Some(after) = rest.strip_prefix("/*") {
+            self.comment_depth = 1;
+            return (2 + self.block_comment_len(after), comment_style());
+        }
+        if let Some(raw) = rest.strip_prefix('r').or_else(|| rest.strip_prefix("br")) {
+            let hashes = raw.chars().take_while(|&c| c == '#').count();
+            if raw[hashes..].starts_with('"') {
+                let prefix = rest.len() - raw.len() + hashes + 1;
+                self.string_end = Some(format!("\"{}", "#".repeat(hashes)));
+                let string_end = self.string_end.clone().unwrap_or_default();
+                return (
+                    prefix + self.string_len(&rest[prefix..], &string_end),
+                    string_style(),
+                );
+            }
+        }
+        if let Some(after) = rest.strip_prefix('"') {
+            self.string_end = Some("\"".to_string());
+            return (1 + self.string_len(after, "\""), string_style());
+        }
+        if rest.starts_with('\'') {
+            return char_or_lifetime(rest);
+        }
+
+        let first = rest.chars().next().unwrap_or_default();
+        if first.is_whitespace() {
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            return (len, Style::default());
+        }
+        if first.is_ascii_digit() {
+            return (number_len(rest), number_style());
+        }
+        if first.is_alphanumeric() || first == '_' {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            let after = &rest[len..];
+            return if after.starts_with('!') && !after.starts_with("!=") {
+                (len + 1, macro_style())
+            } else if KEYWORDS.contains(&word) {
+                (len, keyword_style())
+            } else if word.starts_with(char::is_uppercase) {
+                (len, type_style())
+            } else if after.starts_with('(') {
+                (len, function_style())
+            } else {
+                (len, Style::default())
+            };
+        }
+        (first.len_utf8(), Style::default())
+    }
+
+    /// Measures the part of a block comment on this line, closing the
+    /// comment if it ends here.
+    fn block_comment_len(&mut self, rest: &str) -> usize {
+        let mut index = 0;
+        while index < rest.len() {
+            if rest[index..].starts_with("/*") {
+                self.comment_depth += 1;
+                index += 2;
+            } else if rest[index..].starts_with("*/") {
+                self.comment_depth -= 1;
+                index += 2;
+                if self.comment_depth == 0 {
+                    return index;
+                }
+            } else {
+                index += rest[index..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        rest.len()
+    }
+
+    /// Measures the part of a string literal on this line, closing the
+    /// string if it ends here. Escapes only apply to strings that are not raw.
+    fn string_len(&mut self, rest: &str, string_end: &str) -> usize {
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            if c == '\\' && string_end == "\"" {
+                chars.next();
+            } else if rest[index..].starts_with(string_end) {
+                self.string_end = None;
+                return index + string_end.len();
+            }
+        }
+        rest.len()
+    }
+}
+
+/// Tells a char literal such as `'a'` or `'\n'` apart from a lifetime such
+/// as `'static`, returning the length and style of either.
+fn char_or_lifetime(rest: &str) -> (usize, Style) {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '\\')) => {
+            // Skip the escaped character, which may itself be a quote.
+            let len = rest
+                .get(3..)
+                .and_then(|after| after.find('\''))
+                .map_or(rest.len(), |index| index + 4);
+            (len, string_style())
+        }
+        Some((_, c)) => match chars.next() {
+            Some((index, '\'')) => (index + 1, string_style()),
+            _ if c.is_alphabetic() || c == '_' => {
+                let len = rest[1..]
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .map_or(rest.len(), |index| index + 1);
+                (len, number_style())
+            }
+            _ => (1, Style::default()),
+        },
+        None => (1, Style::default()),
+    }
+}
+
+/// Measures a number literal, including suffixes such as `u64` and the
+/// decimal point of a float, but not the `..` of a range.
+fn number_len(rest: &str) -> usize {
+    let mut len = 0;
+    let bytes = rest.as_bytes();
+    while len < bytes.len() {
+        let c = bytes[len];
+        let is_decimal_point =
+            c == b'.' && bytes.get(len + 1).is_some_and(|next| next.is_ascii_digit());
+        if c.is_ascii_alphanumeric() || c == b'_' || is_decimal_point {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+/// Highlights a whole source file, with line numbers in a gutter.
+pub fn highlight_with_line_numbers(source: &str) -> Vec<Line<'static>> {
+    let mut highlighter = RustHighlighter::default();
+    let width = source.lines().count().to_string().len();
+    source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let mut spans = vec![Span::styled(
+                format!("{:>width$} │ ", index + 1),
+                Style::default().fg(Color::DarkGray),
+            )];
+            spans.extend(highlighter.highlight_line(line).spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn keyword_style() -> Style {
+    Style::default().fg(Color::Magenta)
+}
+
+fn string_style() -> Style {
+    Style::default().fg(Color::Green)
+}
+
+fn comment_style() -> Style {
+    Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC)
+}
+
+fn number_style() -> Style {
+    Style::default().fg(Color::Cyan)
+}
+
+fn macro_style() -> Style {
+    Style::default().fg(Color::LightBlue)
+}
+
+fn type_style() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+
+fn function_style() -> Style {
+    Style::default().fg(Color::Blue)
+}
+
+fn attribute_style() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lists the non-whitespace tokens of a line with their styles.
+    fn tokens(highlighter: &mut RustHighlighter, line: &str) -> Vec<(String, Style)> {
+        highlighter
+            .highlight_line(line)
+            .spans
+            .into_iter()
+            .filter(|span| !span.content.trim().is_empty())
+            .map(|span| (span.content.to_string(), span.style))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_line() {
+        let mut highlighter = RustHighlighter::default();
+        assert_eq!(
+            tokens(
+                &mut highlighter,
+                r#"let x: Vec<u8> = vec![1, 2]; // "done""#
+            ),
+            vec![
+                ("let".to_string(), keyword_style()),
+                ("x".to_string(), Style::default()),
+                (":".to_string(), Style::default()),
+                ("Vec".to_string(), type_style()),
+                ("<".to_string(), Style::default()),
+                ("u8".to_string(), Style::default()),
+                (">".to_string(), Style::default()),
+                ("=".to_string(), Style::default()),
+                ("vec!".to_string(), macro_style()),
+                ("[".to_string(), Style::default()),
+                ("1".to_string(), number_style()),
+                (",".to_string(), Style::default()),
+                ("2".to_string(), number_style()),
+                ("]".to_string(), Style::default()),
+                (";".to_string(), Style::default()),
+                ("// \"done\"".to_string(), comment_style()),
+            ]
+        );
+        assert_eq!(
+            tokens(&mut highlighter, "fn f<'a>(c: char) -> bool { c == 'x' }")[2..4],
+            [
+                ("<".to_string(), Style::default()),
+                ("'a".to_string(), number_style()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_state_carries_over_lines() {
+        let mut highlighter = RustHighlighter::default();
+        tokens(&mut highlighter, "let s = \"first");
+        assert_eq!(
+            tokens(&mut highlighter, "second\"; /* a"),
+            vec![
+                ("second\"".to_string(), string_style()),
+                (";".to_string(), Style::default()),
+                ("/* a".to_string(), comment_style()),
+            ]
+        );
+        assert_eq!(
+            tokens(&mut highlighter, "b */ 0..10"),
+            vec![
+                ("b */".to_string(), comment_style()),
+                ("0".to_string(), number_style()),
+                (".".to_string(), Style::default()),
+                (".".to_string(), Style::default()),
+                ("10".to_string(), number_style()),
+            ]
+        );
+    }
+}
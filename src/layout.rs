@@ -0,0 +1,132 @@
+use crate::header::{Controls, Header};
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// How much the task tree grows or shrinks at a time, as a percentage of the
+/// width of the screen.
+const FINDER_STEP: u16 = 5;
+const MIN_FINDER_PERCENT: u16 = 10;
+const MAX_FINDER_PERCENT: u16 = 70;
+
+/// Where each part of the screen goes.
+pub struct Areas {
+    pub header: Rect,
+    pub controls: Rect,
+    pub finder: Rect,
+    pub preview: Rect,
+}
+
+/// Splits the screen between the banner, the controls, the task tree and
+/// the preview, following the terminal's size and what the user has hidden
+/// or resized.
+pub struct AppLayout {
+    pub show_header: bool,
+    pub show_controls: bool,
+    /// The width of the task tree, as a percentage of the screen's.
+    finder_percent: u16,
+}
+
+impl Default for AppLayout {
+    fn default() -> AppLayout {
+        AppLayout {
+            show_header: true,
+            show_controls: true,
+            finder_percent: 30,
+        }
+    }
+}
+
+impl AppLayout {
+    pub fn toggle_header(&mut self) {
+        self.show_header = !self.show_header;
+    }
+
+    pub fn toggle_controls(&mut self) {
+        self.show_controls = !self.show_controls;
+    }
+
+    /// Grows the task tree by `steps` steps, or shrinks it if `steps` is
+    /// negative, taking the width from the preview.
+    pub fn resize_finder(&mut self, steps: i16) {
+        let percent = self.finder_percent as i16 + steps * FINDER_STEP as i16;
+        self.finder_percent =
+            percent.clamp(MIN_FINDER_PERCENT as i16, MAX_FINDER_PERCENT as i16) as u16;
+    }
+
+    pub fn split(&self, area: Rect, header: &Header, controls: &Controls) -> Areas {
+        let header_height = if self.show_header {
+            header.height(area.width)
+        } else {
+            0
+        };
+        // The controls never take more than a third of the screen, so that
+        // there is always room for the tree and the preview.
+        let controls_height = if self.show_controls {
+            controls.height(area.width).min(area.height / 3)
+        } else {
+            0
+        };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_height),
+                Constraint::Length(controls_height),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(self.finder_percent),
+                Constraint::Percentage(100 - self.finder_percent),
+            ])
+            .split(rows[2]);
+
+        Areas {
+            header: rows[0],
+            controls: rows[1],
+            finder: columns[0],
+            preview: columns[1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{KeyBindings, Theme};
+
+    #[test]
+    fn test_split_follows_terminal_size() {
+        let header = Header::new(Theme::default());
+        let controls = Controls::new(&KeyBindings::default(), Theme::default());
+        let mut layout = AppLayout::default();
+
+        let wide = layout.split(Rect::new(0, 0, 200, 50), &header, &controls);
+        assert_eq!(wide.header.height, 5);
+        assert_eq!(wide.finder.width, 60);
+        assert_eq!(wide.preview.width, 140);
+
+        let narrow = layout.split(Rect::new(0, 0, 80, 50), &header, &controls);
+        assert_eq!(narrow.header.height, 1);
+        assert!(narrow.controls.height > wide.controls.height);
+
+        layout.toggle_header();
+        layout.toggle_controls();
+        let hidden = layout.split(Rect::new(0, 0, 80, 50), &header, &controls);
+        assert_eq!(hidden.header.height, 0);
+        assert_eq!(hidden.controls.height, 0);
+        assert_eq!(hidden.finder.height, 50);
+    }
+
+    #[test]
+    fn test_resize_finder_is_bounded() {
+        let mut layout = AppLayout::default();
+        layout.resize_finder(1);
+        assert_eq!(layout.finder_percent, 35);
+        layout.resize_finder(-100);
+        assert_eq!(layout.finder_percent, MIN_FINDER_PERCENT);
+        layout.resize_finder(100);
+        assert_eq!(layout.finder_percent, MAX_FINDER_PERCENT);
+    }
+}
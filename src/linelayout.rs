@@ -0,0 +1,124 @@
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
+
+/// Where each line of some text starts once wrapped to a width, so that the
+/// preview can scroll through long output without wrapping all of it every
+/// frame. Lines are only measured once: text that has just grown is measured
+/// from where it left off, and anything else starts over.
+#[derive(Default)]
+pub struct LineLayout {
+    /// Which text was measured. Callers change it whenever the text changes
+    /// other than by adding lines at the end.
+    version: u64,
+    width: u16,
+    wrap: bool,
+    /// The first visual line of each line, followed by the total.
+    starts: Vec<usize>,
+    widest: usize,
+}
+
+impl LineLayout {
+    /// Measures any lines not yet measured for this version, width and
+    /// wrapping.
+    pub fn update<T: Clone + Into<Line<'static>>>(
+        &mut self,
+        version: u64,
+        width: u16,
+        wrap: bool,
+        lines: &[T],
+    ) {
+        if (version, width, wrap) != (self.version, self.width, self.wrap)
+            || self.starts.is_empty()
+            || lines.len() < self.len()
+        {
+            *self = LineLayout {
+                version,
+                width,
+                wrap,
+                starts: vec![0],
+                widest: 0,
+            };
+        }
+        for line in &lines[self.len()..] {
+            let line: Line = line.clone().into();
+            self.widest = self.widest.max(line.width());
+            let mut paragraph = Paragraph::new(line);
+            if wrap {
+                paragraph = paragraph.wrap(Wrap { trim: false });
+            }
+            self.starts.push(self.total() + paragraph.line_count(width));
+        }
+    }
+
+    /// The number of lines measured.
+    fn len(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+
+    /// The number of visual lines the text takes up.
+    pub fn total(&self) -> usize {
+        self.starts.last().copied().unwrap_or(0)
+    }
+
+    /// The width of the widest line.
+    pub fn widest(&self) -> usize {
+        self.widest
+    }
+
+    /// The visual line a line starts on.
+    pub fn start_of(&self, line: usize) -> usize {
+        self.starts
+            .get(line)
+            .copied()
+            .unwrap_or_else(|| self.total())
+    }
+
+    /// The line shown at a visual line, or the number of lines if the visual
+    /// line is past the end.
+    pub fn line_at(&self, visual_line: usize) -> usize {
+        self.starts
+            .partition_point(|&start| start <= visual_line)
+            .saturating_sub(1)
+            .min(self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapped_lines() {
+        let lines = ["short", "a line that wraps onto three rows", "", "end"];
+        let mut layout = LineLayout::default();
+        layout.update(1, 12, true, &lines.map(String::from));
+        assert_eq!(layout.total(), 6);
+        assert_eq!(layout.widest(), 33);
+        assert_eq!(layout.start_of(2), 4);
+        assert_eq!(layout.line_at(0), 0);
+        assert_eq!(layout.line_at(3), 1);
+        assert_eq!(layout.line_at(4), 2);
+        assert_eq!(layout.line_at(6), 4);
+
+        layout.update(1, 12, false, &lines.map(String::from));
+        assert_eq!(layout.total(), 4);
+        assert_eq!(layout.line_at(1), 1);
+    }
+
+    #[test]
+    fn test_only_new_lines_are_measured() {
+        let mut lines = vec![Line::from("one"), Line::from("two")];
+        let mut layout = LineLayout::default();
+        layout.update(1, 80, true, &lines);
+        // A line measured before keeps its height until the version changes,
+        // even if it is replaced.
+        lines[0] = Line::from("x ".repeat(60));
+        lines.push(Line::from("three"));
+        layout.update(1, 80, true, &lines);
+        assert_eq!(layout.total(), 3);
+        layout.update(2, 80, true, &lines);
+        assert_eq!(layout.total(), 4);
+        layout.update(2, 80, true, &lines[..1]);
+        assert_eq!(layout.total(), 2);
+    }
+}
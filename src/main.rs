@@ -1,30 +1,85 @@
+mod ansi;
+mod answers;
+mod batch;
+mod buildmessages;
+mod cli;
+mod config;
+mod diff;
+mod fuzzy;
 mod header;
+mod highlight;
+mod layout;
+mod linelayout;
+mod markdown;
+mod outputsearch;
+mod palette;
+mod runhistory;
+mod session;
 mod taskfinder;
 mod taskpreview;
+mod taskrunner;
+mod testreport;
 
+use batch::{BatchRun, ExportFormat};
+use config::{Action, Config, Key};
 use header::{Controls, Header};
+use layout::AppLayout;
+use palette::CommandPalette;
+use runhistory::RunHistory;
+use session::Session;
 use taskfinder::TaskFinder;
-use taskpreview::TaskPreview;
+use taskpreview::{PreviewTab, TaskPreview};
 
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+};
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, ScrollbarState};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Terminal;
 use std::io;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
+/// How many columns the preview scrolls sideways when lines are not
+/// wrapped.
+const HORIZONTAL_SCROLL: isize = 10;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let app = Arc::new(Mutex::new(App::new()));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        match cli::run(&args).await {
+            Ok(code) => std::process::exit(code),
+            Err(message) => {
+                eprintln!("{}", message);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    // Check the config before taking over the terminal, so that mistakes in
+    // it are printed where they can be read.
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+
+    let app = Arc::new(Mutex::new(App::new(config)));
+    // Build the aoc runner in the background, so that it is usually ready by
+    // the first run and the tree can mark the tasks that are not registered.
+    tokio::spawn(taskrunner::load_solutions());
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -54,15 +109,78 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 struct App {
     pub task_finder: TaskFinder,
     task_preview: TaskPreview,
+    run_history: RunHistory,
+    show_history: bool,
+    /// The run of every task shown in a popup, if one has been started.
+    batch: Option<BatchRun>,
     error_message: Option<String>,
+    /// The command palette, while it is open.
+    palette: Option<CommandPalette>,
+    layout: AppLayout,
+    header: Header,
+    controls: Controls,
+    config: Config,
 }
 
 impl App {
-    fn new() -> App {
+    /// Creates the app as it was left by the last session.
+    fn new(config: Config) -> App {
+        let session = Session::load();
+        let mut task_finder = TaskFinder::new();
+        task_finder.restore(&session);
+        let mut task_preview = TaskPreview::new(&config.keys);
+        task_preview.show_selected(task_finder.selected_path());
+        if let Some(tab) = session
+            .active_tab
+            .as_deref()
+            .and_then(PreviewTab::from_name)
+        {
+            task_preview.restore_view(tab, session.scroll_offset);
+        }
+
         App {
-            task_finder: TaskFinder::new(),
-            task_preview: TaskPreview::new(),
+            task_finder,
+            task_preview,
+            run_history: RunHistory::load(),
+            show_history: false,
+            batch: None,
             error_message: None,
+            palette: None,
+            layout: AppLayout::default(),
+            header: Header::new(config.theme),
+            controls: Controls::new(&config.keys, config.theme),
+            config,
+        }
+    }
+
+    /// The task the run history popup should show: the selected task in the
+    /// tree, or else the task last run in the preview.
+    fn history_task_path(&self) -> Option<String> {
+        match self.task_finder.selected_task() {
+            Some((task_path, _)) => Some(task_path),
+            None => self.task_preview.task_path.clone(),
+        }
+    }
+
+    /// Saves what is shown so that the next launch can pick up from here.
+    fn save_session(&self) -> io::Result<()> {
+        let mut session = Session {
+            active_tab: Some(self.task_preview.active_tab.name().to_string()),
+            scroll_offset: self.task_preview.scroll_offset(),
+            ..Session::default()
+        };
+        self.task_finder.save(&mut session);
+        session.save()
+    }
+
+    /// Reopens the run selected in the history popup in the preview.
+    fn reopen_run(&mut self) {
+        let Some(task_path) = self.history_task_path() else {
+            return;
+        };
+        if let Some((number, run)) = self.run_history.selected_run(&task_path) {
+            self.task_preview.show_run(run, number);
+            self.show_history = false;
         }
     }
 
@@ -94,39 +212,79 @@ async fn run_app<B: ratatui::backend::Backend>(
     app: Arc<Mutex<App>>,
 ) -> io::Result<()> {
     loop {
-        let mut app = app.lock().await;
+        let mut guard = app.lock().await;
+        let app = &mut *guard;
+        if let Some(record) = app.task_preview.poll_output() {
+            if let Some(error_message) = app.task_preview.failure_message(record.status) {
+                app.error_message = Some(error_message);
+            }
+            if let Some(verdict) = &record.verdict {
+                let input_name = answers::input_name(record.input_file.as_deref());
+                app.task_finder
+                    .set_verdict(&record.task_path, &input_name, verdict.clone());
+            }
+            app.run_history.push(record);
+            if let Err(e) = app.run_history.save() {
+                app.error_message = Some(format!(
+                    "Failed to save {}: {}",
+                    runhistory::HISTORY_FILE,
+                    e
+                ));
+            }
+        }
+        if let Some(results) = app.task_preview.poll_tests() {
+            app.task_finder.set_test_results(&results);
+        }
+        if let Some(batch) = app.batch.as_mut() {
+            batch.poll();
+        }
+        app.task_finder
+            .set_registered(taskrunner::registered_solutions());
+        let selected_path = app.task_finder.selected_path();
+        app.task_preview.show_selected(selected_path);
+        let theme = app.config.theme;
         terminal.draw(|f| {
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Length(6), Constraint::Min(0)].as_ref())
-                .split(f.area());
-
-            let header_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(80), Constraint::Percentage(20)].as_ref())
-                .split(chunks[0]);
-
-            let header = Header::new();
-            let controls = Controls::new();
-            f.render_widget(header, header_chunks[0]);
-            f.render_widget(controls, header_chunks[1]);
-
-            let main_chunks = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
-                .split(chunks[1]);
-
-            app.task_finder
-                .render::<CrosstermBackend<std::io::Stdout>>(f, main_chunks[0]);
+            let areas = app.layout.split(f.area(), &app.header, &app.controls);
+            f.render_widget(&app.header, areas.header);
+            f.render_widget(&app.controls, areas.controls);
+
+            app.task_finder.render(f, areas.finder, theme);
             app.task_preview
-                .render::<CrosstermBackend<std::io::Stdout>>(f, main_chunks[1]);
+                .render(f, areas.preview, &app.config.keys, theme);
+
+            if app.show_history {
+                if let Some(task_path) = app.history_task_path() {
+                    let area = popup_area(f.area(), 60, 50);
+                    f.render_widget(Clear, area);
+                    app.run_history
+                        .render(f, area, &task_path, &app.config.keys);
+                }
+            }
+
+            if let Some(batch) = &app.batch {
+                let area = popup_area(f.area(), 80, 80);
+                f.render_widget(Clear, area);
+                batch.render(f, area, &app.config.keys);
+            }
+
+            if let Some(palette) = &app.palette {
+                let area = popup_area(f.area(), 80, 60);
+                f.render_widget(Clear, area);
+                palette.render(f, area, &app.config.keys, theme);
+            }
 
             if let Some(error_message) = &app.error_message {
                 let error_block = Block::default()
                     .borders(Borders::ALL)
                     .title("Error")
                     .border_style(Style::default().fg(Color::Red));
-                let error_message_text = format!("{}\n\nPress Enter to close", error_message);
+                let error_message_text = format!(
+                    "{}\n\n{}",
+                    error_message,
+                    app.config
+                        .keys
+                        .describe(&[Action::Run, Action::Close], "close")
+                );
                 let error_paragraph =
                     Paragraph::new(error_message_text.as_str()).block(error_block);
                 let area = Layout::default()
@@ -156,57 +314,324 @@ async fn run_app<B: ratatui::backend::Backend>(
             }
         })?;
 
-        if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-            let mut app = app;
-            match code {
-                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                KeyCode::Down | KeyCode::Char('s') => {
+        // Poll with a timeout rather than blocking on read so that output from
+        // a running task keeps being drawn while no keys are pressed.
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                app.task_preview.handle_mouse(mouse);
+                continue;
+            }
+            _ => continue,
+        };
+        // The search prompts and the palette take the keys as text while
+        // they are open.
+        if app.task_preview.editing_output_search() {
+            match key.code {
+                KeyCode::Esc => app.task_preview.clear_output_search(),
+                KeyCode::Enter => app.task_preview.confirm_output_search(),
+                KeyCode::Backspace => app.task_preview.pop_output_search_char(),
+                KeyCode::Char(c) => app.task_preview.push_output_search_char(c),
+                _ => {}
+            }
+            continue;
+        }
+        if app.task_finder.searching {
+            match key.code {
+                KeyCode::Esc => app.task_finder.clear_search(),
+                KeyCode::Enter => app.task_finder.confirm_search(),
+                KeyCode::Backspace => app.task_finder.pop_search_char(),
+                KeyCode::Down => {
                     app.task_finder.file_tree_state.key_down();
                 }
-                KeyCode::Up | KeyCode::Char('w') => {
+                KeyCode::Up => {
                     app.task_finder.file_tree_state.key_up();
                 }
-                KeyCode::Right | KeyCode::Char('d') => {
-                    app.task_finder.file_tree_state.key_right();
+                KeyCode::Char(c) => app.task_finder.push_search_char(c),
+                _ => {}
+            }
+            continue;
+        }
+
+        let bound = app.config.keys.action(Key::from_event(key));
+        // Chords are never typed into the palette, and the one that opens it
+        // closes it again.
+        let chord = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let action = match app.palette.as_mut() {
+            Some(_)
+                if bound == Some(Action::Close) || (chord && bound == Some(Action::Palette)) =>
+            {
+                app.palette = None;
+                None
+            }
+            Some(_) if bound == Some(Action::Run) => {
+                app.palette.take().and_then(|p| p.selected_action())
+            }
+            Some(palette) => match key.code {
+                KeyCode::Backspace => {
+                    palette.pop_char();
+                    None
                 }
-                KeyCode::Left | KeyCode::Char('a') => {
-                    app.task_finder.file_tree_state.key_left();
+                KeyCode::Down => {
+                    palette.select_next();
+                    None
                 }
-                KeyCode::Enter => {
-                    if app.error_message.is_some() {
-                        app.error_message = None;
-                        return Ok(());
-                    }
-
-                    let file_path = app.task_finder.file_tree_state.selected().join("/");
-                    if file_path.contains("task") {
-                        if let Err(e) = app.task_preview.run_task(&file_path).await {
-                            app.error_message = Some(format!("Failed to run task: {}", e));
-                        }
-                    }
+                KeyCode::Up => {
+                    palette.select_previous();
+                    None
                 }
-                KeyCode::PageUp => {
-                    if app.task_preview.scroll_offset > 10 {
-                        app.task_preview.scroll_offset -= 10;
-                    } else {
-                        app.task_preview.scroll_offset = 0;
-                    }
-                    app.task_preview.scrollbar_state =
-                        ScrollbarState::new(app.task_preview.total_lines)
-                            .position(app.task_preview.scroll_offset);
+                KeyCode::Char(c) if !chord => {
+                    palette.push_char(c);
+                    None
                 }
-                KeyCode::PageDown => {
-                    if app.task_preview.scroll_offset + 10 < app.task_preview.total_lines {
-                        app.task_preview.scroll_offset += 10;
-                    } else {
-                        app.task_preview.scroll_offset = app.task_preview.total_lines;
-                    }
-                    app.task_preview.scrollbar_state =
-                        ScrollbarState::new(app.task_preview.total_lines)
-                            .position(app.task_preview.scroll_offset);
+                _ => None,
+            },
+            None => bound,
+        };
+        let Some(action) = action else {
+            continue;
+        };
+        if perform(terminal, app, action) {
+            if let Err(e) = app.save_session() {
+                app.log_error(&format!("Failed to save {}: {}", session::SESSION_FILE, e))
+                    .await;
+            }
+            return Ok(());
+        }
+    }
+}
+
+/// Does what an action asks of the app. What some actions do depends on what
+/// is shown, e.g. scrolling selects runs while the run history is open.
+///
+/// Returns true if the app should quit.
+fn perform<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    action: Action,
+) -> bool {
+    match action {
+        Action::Close if app.error_message.is_some() => {
+            app.error_message = None;
+        }
+        Action::Close if app.batch.is_some() => {
+            // Dropping the batch stops any tasks still running.
+            app.batch = None;
+        }
+        Action::Close if app.show_history => {
+            app.show_history = false;
+        }
+        Action::Close if app.task_preview.has_output_search() => {
+            app.task_preview.clear_output_search();
+        }
+        Action::Close if app.task_finder.query.is_some() => {
+            app.task_finder.clear_search();
+        }
+        Action::Quit | Action::Close => return true,
+        Action::Run if app.error_message.is_some() => {
+            app.error_message = None;
+        }
+        Action::Run if app.show_history => app.reopen_run(),
+        Action::ScrollDown | Action::ScrollUp | Action::Toggle | Action::Compare
+            if app.show_history =>
+        {
+            let Some(task_path) = app.history_task_path() else {
+                return false;
+            };
+            match action {
+                Action::ScrollDown => app.run_history.select_next(&task_path),
+                Action::ScrollUp => app.run_history.select_previous(&task_path),
+                Action::Toggle => app.run_history.toggle_mark(&task_path),
+                _ => app
+                    .run_history
+                    .toggle_comparison(&task_path, &app.config.keys),
+            }
+        }
+        Action::Search if app.task_preview.shows_output() => {
+            app.task_preview.start_output_search();
+        }
+        Action::Search => {
+            app.task_finder.start_search();
+        }
+        Action::NextMatch => app.task_preview.next_match(),
+        Action::PreviousMatch => app.task_preview.previous_match(),
+        Action::Down => {
+            app.task_finder.file_tree_state.key_down();
+        }
+        Action::Up => {
+            app.task_finder.file_tree_state.key_up();
+        }
+        Action::Right => {
+            app.task_finder.file_tree_state.key_right();
+        }
+        Action::Left => {
+            app.task_finder.file_tree_state.key_left();
+        }
+        Action::Run => {
+            if let Some((task_path, input_file)) = app.task_finder.selected_task() {
+                let input_file = app.task_finder.choose_input(&task_path, input_file);
+                if let Err(e) = app.task_preview.run_task(&task_path, input_file.as_deref()) {
+                    app.error_message = Some(format!("Failed to run task: {}", e));
                 }
-                _ => {}
             }
         }
+        Action::Test => {
+            let task_paths = app.task_finder.selected_test_paths();
+            if task_paths.is_empty() {
+                return false;
+            }
+            if let Err(e) = app.task_preview.run_tests(task_paths) {
+                app.error_message = Some(format!("Failed to run tests: {}", e));
+            }
+        }
+        Action::ScrollDown if app.task_preview.active_tab == PreviewTab::Tests => {
+            app.task_preview.select_next_test();
+        }
+        Action::ScrollUp if app.task_preview.active_tab == PreviewTab::Tests => {
+            app.task_preview.select_previous_test();
+        }
+        Action::Toggle if app.task_preview.active_tab == PreviewTab::Tests => {
+            app.task_preview.toggle_test();
+        }
+        Action::Edit => {
+            if let Some(source_file) = app.task_preview.source_file.clone() {
+                if let Err(e) = open_in_editor(terminal, &source_file) {
+                    app.error_message = Some(format!("Failed to open editor: {}", e));
+                }
+                app.task_preview.reload_source();
+            }
+        }
+        Action::Cancel => {
+            app.task_preview.cancel_task();
+        }
+        Action::Colours => {
+            app.task_preview.toggle_colours();
+        }
+        Action::NextTab => {
+            app.task_preview.toggle_tab();
+        }
+        Action::History => {
+            app.show_history = !app.show_history;
+            app.run_history.reset_selection();
+        }
+        Action::RunAll => {
+            // Closing the table drops the batch, which stops any tasks still
+            // running.
+            app.batch = match app.batch {
+                Some(_) => None,
+                None => Some(BatchRun::start(taskfinder::all_tasks(), app.config.jobs)),
+            };
+        }
+        Action::ExportMarkdown | Action::ExportCsv if app.batch.is_some() => {
+            let format = if action == Action::ExportMarkdown {
+                ExportFormat::Markdown
+            } else {
+                ExportFormat::Csv
+            };
+            if let Some(Err(e)) = app.batch.as_mut().map(|batch| batch.export(format)) {
+                app.error_message = Some(format!("Failed to write {}: {}", format.file_name(), e));
+            }
+        }
+        Action::PageUp => app.task_preview.scroll_pages(-1),
+        Action::PageDown => app.task_preview.scroll_pages(1),
+        Action::Top => app.task_preview.scroll_to_top(),
+        Action::Bottom => app.task_preview.scroll_to_bottom(),
+        Action::ScrollDown => app.task_preview.scroll_by(1),
+        Action::ScrollUp => app.task_preview.scroll_by(-1),
+        Action::ScrollLeft => app.task_preview.scroll_horizontally(-HORIZONTAL_SCROLL),
+        Action::ScrollRight => app.task_preview.scroll_horizontally(HORIZONTAL_SCROLL),
+        Action::Wrap => app.task_preview.toggle_wrap(),
+        Action::Palette => app.palette = Some(CommandPalette::default()),
+        Action::ToggleHeader => app.layout.toggle_header(),
+        Action::ToggleControls => app.layout.toggle_controls(),
+        Action::ShrinkFinder => app.layout.resize_finder(-1),
+        Action::GrowFinder => app.layout.resize_finder(1),
+        Action::Toggle | Action::Compare | Action::ExportMarkdown | Action::ExportCsv => {}
+    }
+    false
+}
+
+/// Opens a file in `$EDITOR`, or `vi` if it is not set, with the TUI
+/// suspended until the editor exits.
+fn open_in_editor<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    path: &str,
+) -> io::Result<()> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    // The editor may be given with arguments, e.g. "code --wait".
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(path)
+        .status();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+
+    match status? {
+        status if status.success() => Ok(()),
+        status => Err(io::Error::other(format!(
+            "{} exited with {}",
+            program, status
+        ))),
+    }
+}
+
+/// Returns a rectangle centred in `area` taking up the given percentages of
+/// its width and height.
+fn popup_area(area: Rect, width_percent: u16, height_percent: u16) -> Rect {
+    let area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - height_percent) / 2),
+                Constraint::Percentage(height_percent),
+                Constraint::Percentage((100 - height_percent) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area)[1];
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - width_percent) / 2),
+                Constraint::Percentage(width_percent),
+                Constraint::Percentage((100 - width_percent) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area)[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    #[tokio::test]
+    async fn test_close_dismisses_popups_before_quitting() {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let mut app = App::new(Config::default());
+
+        app.error_message = Some("Failed to run task".to_string());
+        app.batch = Some(BatchRun::start(Vec::new(), 1));
+        assert!(!perform(&mut terminal, &mut app, Action::Close));
+        assert!(app.error_message.is_none());
+        assert!(app.batch.is_some());
+        assert!(!perform(&mut terminal, &mut app, Action::Close));
+        assert!(app.batch.is_none());
+
+        assert!(perform(&mut terminal, &mut app, Action::Close));
     }
 }
@@ -0,0 +1,167 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// Renders the basic Markdown used in the READMEs and doc comments as styled
+/// lines: headings, bullet and numbered lists, fenced code blocks, and inline
+/// code, bold text and links. Anything else is shown as written.
+pub fn render(markdown: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::styled(format!("    {}", line), code_style()));
+            continue;
+        }
+
+        let heading_level = line.chars().take_while(|&c| c == '#').count();
+        if heading_level > 0 && line[heading_level..].starts_with(' ') {
+            let mut style = Style::default().add_modifier(Modifier::BOLD);
+            if heading_level == 1 {
+                style = style.fg(Color::Green).add_modifier(Modifier::UNDERLINED);
+            }
+            lines.push(Line::styled(
+                line[heading_level..].trim().to_string(),
+                style,
+            ));
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let item = line.trim_start();
+        if let Some(text) = item.strip_prefix("- ").or_else(|| item.strip_prefix("* ")) {
+            let mut spans = vec![Span::raw(format!("{}• ", " ".repeat(indent)))];
+            spans.extend(inline(text));
+            lines.push(Line::from(spans));
+            continue;
+        }
+        if let Some((number, text)) = item.split_once(". ") {
+            if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                let mut spans = vec![Span::raw(format!("{}{}. ", " ".repeat(indent), number))];
+                spans.extend(inline(text));
+                lines.push(Line::from(spans));
+                continue;
+            }
+        }
+
+        lines.push(Line::from(inline(line)));
+    }
+    lines
+}
+
+fn code_style() -> Style {
+    Style::default().fg(Color::Yellow)
+}
+
+fn link_style() -> Style {
+    Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::UNDERLINED)
+}
+
+/// Styles the inline markup of a line: `code`, **bold**, [text](target) and
+/// <https://autolinks>. Unclosed markup is left as plain text.
+fn inline(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => rest[1..]
+                .split_once('`')
+                .map(|(code, after)| (Span::styled(code.to_string(), code_style()), after)),
+            '*' if rest.starts_with("**") => rest[2..].split_once("**").map(|(bold, after)| {
+                let style = Style::default().add_modifier(Modifier::BOLD);
+                (Span::styled(bold.to_string(), style), after)
+            }),
+            '[' => rest[1..].split_once("](").and_then(|(label, after)| {
+                let (_, after) = after.split_once(')')?;
+                Some((Span::styled(label.to_string(), link_style()), after))
+            }),
+            '<' if rest.starts_with("<http") => rest[1..]
+                .split_once('>')
+                .map(|(url, after)| (Span::styled(url.to_string(), link_style()), after)),
+            _ => None,
+        };
+
+        match styled {
+            Some((span, after)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = after;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// Extracts the module documentation from the `//!` comments at the top of a
+/// Rust source file.
+pub fn module_docs(source: &str) -> Option<String> {
+    let docs: Vec<&str> = source
+        .lines()
+        .map_while(|line| line.strip_prefix("//!"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    if docs.is_empty() {
+        None
+    } else {
+        Some(docs.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let lines = render(
+            "# Day 1\n\n<https://adventofcode.com/2024/day/1>\n\n\
+             - [Task 1](task1/src/lib.rs)\n2. Run `cargo test`\n```\nlet x = 1;\n```",
+        );
+        assert_eq!(
+            plain(&lines),
+            vec![
+                "Day 1",
+                "",
+                "https://adventofcode.com/2024/day/1",
+                "",
+                "• Task 1",
+                "2. Run cargo test",
+                "    let x = 1;",
+            ]
+        );
+        assert_eq!(lines[4].spans[1].style, link_style());
+        assert_eq!(lines[5].spans[2].style, code_style());
+    }
+
+    #[test]
+    fn test_module_docs() {
+        let source = "//! Day 1.\n//!\n//! Sums things.\n\nuse std::fmt;\n";
+        assert_eq!(
+            module_docs(source),
+            Some("Day 1.\n\nSums things.".to_string())
+        );
+        assert_eq!(module_docs("use std::fmt;\n"), None);
+    }
+}
@@ -0,0 +1,230 @@
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+/// A match of the search query, as the line it is on and which match of
+/// that line it is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Match {
+    pub line: usize,
+    pub index: usize,
+}
+
+/// A search through a task's output. Matches are found as the output grows,
+/// so a search can be started while the task is still running.
+#[derive(Default)]
+pub struct OutputSearch {
+    pub query: String,
+    /// Whether the query is still being typed.
+    pub editing: bool,
+    pub matches: Vec<Match>,
+    /// The match that was scrolled to, as an index into `matches`.
+    pub current: usize,
+    /// How many lines of the output have been searched.
+    searched_lines: usize,
+}
+
+impl OutputSearch {
+    pub fn new() -> OutputSearch {
+        OutputSearch {
+            editing: true,
+            ..OutputSearch::default()
+        }
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.restart();
+    }
+
+    fn restart(&mut self) {
+        self.matches.clear();
+        self.current = 0;
+        self.searched_lines = 0;
+    }
+
+    /// Searches the lines that have been added since the last call. If the
+    /// output has been cleared in the meantime, the search starts over.
+    pub fn update(&mut self, lines: &[String]) {
+        if lines.len() < self.searched_lines {
+            self.restart();
+        }
+        for (line, text) in lines.iter().enumerate().skip(self.searched_lines) {
+            let count = match_ranges(text, &self.query).len();
+            self.matches
+                .extend((0..count).map(|index| Match { line, index }));
+        }
+        self.searched_lines = lines.len();
+    }
+
+    pub fn current_match(&self) -> Option<Match> {
+        self.matches.get(self.current).copied()
+    }
+
+    /// Makes the first match on or after `line` the current one.
+    pub fn select_from(&mut self, line: usize) {
+        self.current = self
+            .matches
+            .iter()
+            .position(|m| m.line >= line)
+            .unwrap_or(0);
+    }
+
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+
+    /// Describes the search for the preview's title, e.g. `/foo_` while the
+    /// query is typed and `/foo: match 2 of 7` once it is confirmed.
+    pub fn describe(&self) -> String {
+        if self.editing {
+            format!("/{}_", self.query)
+        } else if self.matches.is_empty() {
+            format!("/{}: no matches", self.query)
+        } else {
+            format!(
+                "/{}: match {} of {}",
+                self.query,
+                self.current + 1,
+                self.matches.len()
+            )
+        }
+    }
+
+    /// Highlights the matches in one line of the output, with the current
+    /// match standing out from the rest.
+    pub fn highlight(&self, line_number: usize, line: Line<'static>) -> Line<'static> {
+        let text: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        let ranges = match_ranges(&text, &self.query);
+        if ranges.is_empty() {
+            return line;
+        }
+        let current = self
+            .current_match()
+            .filter(|m| m.line == line_number)
+            .map(|m| m.index);
+
+        // Split the spans at the edges of the matches, keeping their own
+        // styles for the text in between.
+        let mut spans = Vec::new();
+        let mut start = 0;
+        for span in line.spans {
+            let end = start + span.content.len();
+            let mut position = start;
+            while position < end {
+                let in_match = ranges
+                    .iter()
+                    .position(|&(from, to)| from <= position && position < to);
+                let next = match in_match {
+                    Some(index) => ranges[index].1.min(end),
+                    None => ranges
+                        .iter()
+                        .map(|&(from, _)| from)
+                        .find(|&from| from > position)
+                        .map_or(end, |from| from.min(end)),
+                };
+                let content = span.content[position - start..next - start].to_string();
+                let style = match in_match {
+                    Some(index) if Some(index) == current => current_match_style(),
+                    Some(_) => match_style(),
+                    None => span.style,
+                };
+                spans.push(Span::styled(content, style));
+                position = next;
+            }
+            start = end;
+        }
+        Line::from(spans).style(line.style)
+    }
+}
+
+/// Finds the byte ranges of the query in a line. The search ignores case
+/// unless the query has capitals in it.
+pub fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let (text, query) = if ignore_case {
+        (text.to_ascii_lowercase(), query.to_ascii_lowercase())
+    } else {
+        (text.to_string(), query.to_string())
+    };
+    text.match_indices(&query)
+        .map(|(start, found)| (start, start + found.len()))
+        .collect()
+}
+
+fn match_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::Yellow)
+}
+
+fn current_match_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::LightRed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_and_navigate() {
+        let mut search = OutputSearch::new();
+        search.set_query("mul".to_string());
+        let mut lines = vec!["mul(2,4)".to_string(), "don't()".to_string()];
+        search.update(&lines);
+        lines.push("MUL(5,5) mul(8,5)".to_string());
+        search.update(&lines);
+        assert_eq!(search.matches.len(), 3);
+
+        search.select_from(1);
+        assert_eq!(search.current_match(), Some(Match { line: 2, index: 0 }));
+        search.next();
+        search.next();
+        assert_eq!(search.current_match(), Some(Match { line: 0, index: 0 }));
+        search.previous();
+        assert_eq!(search.describe(), "/mul_");
+        search.editing = false;
+        assert_eq!(search.describe(), "/mul: match 3 of 3");
+
+        search.update(&[]);
+        assert!(search.matches.is_empty());
+        assert_eq!(match_ranges("Mul mul", "Mul"), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn test_highlight() {
+        let mut search = OutputSearch::new();
+        search.set_query("ab".to_string());
+        search.update(&["xabyab".to_string()]);
+        search.next();
+        let line = Line::from(vec![Span::raw("xa"), Span::styled("byab", Color::Green)]);
+        let spans: Vec<(String, Style)> = search
+            .highlight(0, line)
+            .spans
+            .into_iter()
+            .map(|span| (span.content.to_string(), span.style))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("x".to_string(), Style::default()),
+                ("a".to_string(), match_style()),
+                ("b".to_string(), match_style()),
+                ("y".to_string(), Style::default().fg(Color::Green)),
+                ("ab".to_string(), current_match_style()),
+            ]
+        );
+    }
+}
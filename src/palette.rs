@@ -0,0 +1,157 @@
+use crate::config::{Action, KeyBindings, Theme};
+use crate::fuzzy;
+
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+/// A prompt for running any action by name, for the actions whose keys are
+/// not remembered.
+#[derive(Default)]
+pub struct CommandPalette {
+    query: String,
+    /// The selected action, as an index into `matches`.
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Lists the actions that match the query, best first. Actions are
+    /// matched by what they do as well as by name.
+    pub fn matches(&self) -> Vec<Action> {
+        let mut matches: Vec<(i64, Action)> = Action::ALL
+            .into_iter()
+            .filter(|action| *action != Action::Palette)
+            .filter_map(|action| {
+                let score = fuzzy::score(&self.query, action.name())
+                    .max(fuzzy::score(&self.query, action.description()))?;
+                Some((score, action))
+            })
+            .collect();
+        if !self.query.trim().is_empty() {
+            matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+
+    pub fn selected_action(&self) -> Option<Action> {
+        self.matches().get(self.selected).copied()
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.matches().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn render(
+        &self,
+        f: &mut ratatui::Frame,
+        area: ratatui::layout::Rect,
+        keys: &KeyBindings,
+        theme: Theme,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary))
+            .title("Commands")
+            .title_bottom(format!(
+                "{}, {}",
+                keys.describe(&[Action::Run], "run"),
+                keys.describe(&[Action::Close], "close")
+            ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner);
+
+        f.render_widget(Paragraph::new(format!(":{}_", self.query)), chunks[0]);
+
+        let matches = self.matches();
+        // Keep the selected action in view once the list is longer than the
+        // popup.
+        let height = usize::from(chunks[1].height).max(1);
+        let first = (self.selected + 1).saturating_sub(height);
+        let lines: Vec<Line> = matches
+            .iter()
+            .enumerate()
+            .skip(first)
+            .map(|(index, action)| {
+                let bound: Vec<String> = keys.keys(*action).iter().map(|key| key.name()).collect();
+                let mut line = Line::from(vec![
+                    Span::raw(format!("{:<52}", action.description())),
+                    Span::styled(format!("{:<16}", action.name()), Color::DarkGray),
+                    Span::styled(bound.join(", "), Color::Yellow),
+                ]);
+                if index == self.selected {
+                    line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+                }
+                line
+            })
+            .collect();
+        let lines = if lines.is_empty() {
+            vec![Line::from("No matching commands")]
+        } else {
+            lines
+        };
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let mut palette = CommandPalette::default();
+        assert_eq!(palette.matches().len(), Action::ALL.len() - 1);
+        assert_eq!(palette.selected_action(), Some(Action::Quit));
+
+        "edit".chars().for_each(|c| palette.push_char(c));
+        assert_eq!(palette.selected_action(), Some(Action::Edit));
+        palette.pop_char();
+        palette.pop_char();
+        palette.pop_char();
+        palette.pop_char();
+        "history".chars().for_each(|c| palette.push_char(c));
+        assert_eq!(palette.selected_action(), Some(Action::History));
+
+        "zzz".chars().for_each(|c| palette.push_char(c));
+        assert_eq!(palette.selected_action(), None);
+    }
+
+    #[test]
+    fn test_selection_stays_within_matches() {
+        let mut palette = CommandPalette::default();
+        palette.select_previous();
+        assert_eq!(palette.selected_action(), Some(Action::Quit));
+
+        "export".chars().for_each(|c| palette.push_char(c));
+        let matches = palette.matches();
+        assert!(matches.starts_with(&[Action::ExportCsv, Action::ExportMarkdown]));
+        (0..matches.len() + 3).for_each(|_| palette.select_next());
+        assert_eq!(palette.selected_action(), matches.last().copied());
+        palette.pop_char();
+        assert_eq!(
+            palette.selected_action(),
+            palette.matches().first().copied()
+        );
+    }
+}
@@ -0,0 +1,563 @@
+use crate::answers::Verdict;
+use crate::config::{Action, KeyBindings};
+use crate::diff;
+use crate::taskpreview::TaskStatus;
+use crate::taskrunner::RunStats;
+
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where the run history is kept between sessions, relative to the project
+/// directory.
+pub const HISTORY_FILE: &str = ".aoc/history.json";
+
+/// How many runs of each task are kept. Older runs are dropped first.
+const MAX_RUNS_PER_TASK: usize = 10;
+
+/// How many lines of a run's output are kept, so that a task printing a huge
+/// map does not bloat the history file.
+const MAX_OUTPUT_LINES: usize = 5000;
+
+/// A finished run of a task, as saved in the history file.
+#[derive(Serialize, Deserialize)]
+pub struct RunRecord {
+    #[serde(rename = "task")]
+    pub task_path: String,
+    #[serde(rename = "input", default)]
+    pub input_file: Option<String>,
+    #[serde(serialize_with = "status_tag", deserialize_with = "from_status_tag")]
+    pub status: TaskStatus,
+    #[serde(flatten)]
+    pub stats: RunStats,
+    /// The answer the task gave, if one could be found.
+    #[serde(default)]
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub verdict: Option<Verdict>,
+    /// What the task printed to stdout, including colour codes.
+    #[serde(default, serialize_with = "truncated_output")]
+    pub output: String,
+    #[serde(
+        serialize_with = "unix_seconds",
+        deserialize_with = "from_unix_seconds"
+    )]
+    pub finished_at: SystemTime,
+}
+
+impl RunRecord {
+    /// The run's stdout without colour codes, for comparing runs.
+    fn plain_output(&self) -> String {
+        strip_ansi_escapes::strip_str(&self.output)
+    }
+}
+
+/// The last runs of every task, grouped by task path, along with the run
+/// selected in the history popup.
+#[derive(Default)]
+pub struct RunHistory {
+    runs: HashMap<String, Vec<RunRecord>>,
+    /// The selected run of the task shown, or `None` for its latest run.
+    selected: Option<usize>,
+    /// A run marked to be compared with the selected one.
+    marked: Option<usize>,
+    /// The task whose runs are selected and marked, so that they can be kept
+    /// on the same runs as older ones are dropped.
+    selection_task: Option<String>,
+    /// The differences between the marked and the selected run, once asked
+    /// for.
+    comparison: Option<Vec<Line<'static>>>,
+}
+
+impl RunHistory {
+    /// Reads the history kept by earlier sessions. A missing or unreadable
+    /// file just means there is no history yet.
+    pub fn load() -> RunHistory {
+        std::fs::read_to_string(HISTORY_FILE)
+            .map(|contents| RunHistory::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(dir) = Path::new(HISTORY_FILE).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(HISTORY_FILE, serde_json::to_string(&self.records())?)
+    }
+
+    /// Every run, ordered by task and then from oldest to latest, as they are
+    /// saved.
+    fn records(&self) -> Vec<&RunRecord> {
+        let mut task_paths: Vec<&String> = self.runs.keys().collect();
+        task_paths.sort();
+        task_paths
+            .into_iter()
+            .flat_map(|task_path| &self.runs[task_path])
+            .collect()
+    }
+
+    /// Reads the runs saved in a history file. Runs that this version does not
+    /// understand are skipped rather than losing the rest.
+    fn parse(contents: &str) -> RunHistory {
+        let records: Vec<serde_json::Value> = serde_json::from_str(contents).unwrap_or_default();
+        let mut history = RunHistory::default();
+        for record in records
+            .into_iter()
+            .filter_map(|record| serde_json::from_value(record).ok())
+        {
+            history.push(record);
+        }
+        history
+    }
+
+    /// Adds a run to its task's history. A selection in the open popup stays
+    /// on the run it was on, or moves to the oldest run left if its run is
+    /// dropped to make room. A comparison with a dropped run is hidden.
+    pub fn push(&mut self, record: RunRecord) {
+        let task_path = record.task_path.clone();
+        let runs = self.runs.entry(task_path.clone()).or_default();
+        runs.push(record);
+        if runs.len() > MAX_RUNS_PER_TASK {
+            runs.remove(0);
+            if self.selection_task.as_ref() == Some(&task_path) {
+                if self.selected == Some(0) || self.marked == Some(0) {
+                    self.comparison = None;
+                }
+                self.selected = match self.selected {
+                    // The selected run was dropped. No selection would mean
+                    // the latest run, so select the oldest one left instead.
+                    Some(0) => Some(0),
+                    selected => selected.map(|index| index - 1),
+                };
+                self.marked = self.marked.and_then(|index| index.checked_sub(1));
+            }
+        }
+    }
+
+    pub fn runs(&self, task_path: &str) -> &[RunRecord] {
+        self.runs.get(task_path).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The index of the selected run of a task, if it has any runs.
+    fn selected_index(&self, task_path: &str) -> Option<usize> {
+        let last = self.runs(task_path).len().checked_sub(1)?;
+        Some(self.selected.unwrap_or(last).min(last))
+    }
+
+    /// The selected run of a task along with its number in the history.
+    pub fn selected_run(&self, task_path: &str) -> Option<(usize, &RunRecord)> {
+        let index = self.selected_index(task_path)?;
+        Some((index + 1, self.runs(task_path).get(index)?))
+    }
+
+    pub fn select_next(&mut self, task_path: &str) {
+        self.selection_task = Some(task_path.to_string());
+        if let Some(index) = self.selected_index(task_path) {
+            self.selected = Some((index + 1).min(self.runs(task_path).len() - 1));
+        }
+    }
+
+    pub fn select_previous(&mut self, task_path: &str) {
+        self.selection_task = Some(task_path.to_string());
+        if let Some(index) = self.selected_index(task_path) {
+            self.selected = Some(index.saturating_sub(1));
+        }
+    }
+
+    /// Forgets the selection, e.g. when the popup shows another task.
+    pub fn reset_selection(&mut self) {
+        self.selected = None;
+        self.marked = None;
+        self.selection_task = None;
+        self.comparison = None;
+    }
+
+    /// Marks the selected run, or unmarks it if it is already marked.
+    pub fn toggle_mark(&mut self, task_path: &str) {
+        self.selection_task = Some(task_path.to_string());
+        let selected = self.selected_index(task_path);
+        self.marked = if self.marked == selected {
+            None
+        } else {
+            selected
+        };
+        self.comparison = None;
+    }
+
+    /// Compares the output of the marked run with the selected one, or hides
+    /// the comparison if it is shown.
+    pub fn toggle_comparison(&mut self, task_path: &str, keys: &KeyBindings) {
+        if self.comparison.take().is_some() {
+            return;
+        }
+        let runs = self.runs(task_path);
+        let (Some(marked), Some(selected)) = (self.marked, self.selected_index(task_path)) else {
+            self.comparison = Some(vec![Line::from(format!(
+                "Mark a run and select another to compare it with ({}, {})",
+                keys.describe(&[Action::Toggle], "mark"),
+                keys.describe(&[Action::Compare], "compare")
+            ))]);
+            return;
+        };
+        let (Some(old), Some(new)) = (runs.get(marked), runs.get(selected)) else {
+            return;
+        };
+        let (old_output, new_output) = (old.plain_output(), new.plain_output());
+        let mut lines = vec![
+            Line::styled(format!("--- run #{}", marked + 1), Color::Red),
+            Line::styled(format!("+++ run #{}", selected + 1), Color::Green),
+        ];
+        lines.extend(diff::unified(&diff::diff_lines(&old_output, &new_output)));
+        self.comparison = Some(lines);
+    }
+
+    pub fn render(
+        &self,
+        f: &mut ratatui::Frame,
+        area: ratatui::layout::Rect,
+        task_path: &str,
+        keys: &KeyBindings,
+    ) {
+        let runs = self.runs(task_path);
+        let selected = self.selected_index(task_path);
+        let now = SystemTime::now();
+        let lines = if runs.is_empty() {
+            vec![Line::from("No runs yet")]
+        } else {
+            runs.iter()
+                .enumerate()
+                .map(|(i, run)| {
+                    let status = run.status.describe().unwrap_or_default();
+                    let mark = if self.marked == Some(i) { "*" } else { " " };
+                    let mut spans = vec![
+                        Span::raw(format!("{}#{:<3} ", mark, i + 1)),
+                        Span::raw(format!("{:<9} ", format_age(now, run.finished_at))),
+                        Span::styled(
+                            format!("{:<18}", status),
+                            Style::default().fg(run.status.color()),
+                        ),
+                        Span::raw(run.stats.summary()),
+                        Span::raw(
+                            run.input_file
+                                .as_ref()
+                                .map(|input_file| format!(" [{}]", input_file))
+                                .unwrap_or_default(),
+                        ),
+                    ];
+                    if let Some(answer) = &run.answer {
+                        spans.push(Span::raw(format!(" = {}", answer)));
+                    }
+                    spans.extend(run.verdict.as_ref().map(Verdict::badge));
+                    let line = Line::from(spans);
+                    if selected == Some(i) {
+                        line.style(Style::default().add_modifier(Modifier::REVERSED))
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title(format!(
+                "Run history: {} ({}, {}, {}, {})",
+                task_path,
+                keys.describe(&[Action::ScrollDown, Action::ScrollUp], "select"),
+                keys.describe(&[Action::Run], "reopen"),
+                keys.describe(&[Action::Toggle], "mark"),
+                keys.describe(&[Action::Compare], "compare")
+            ));
+        let Some(comparison) = &self.comparison else {
+            f.render_widget(Paragraph::new(lines).block(block), area);
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(runs.len().max(1) as u16 + 2),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        f.render_widget(Paragraph::new(lines).block(block), chunks[0]);
+        let comparison_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .title("Compared output");
+        f.render_widget(
+            Paragraph::new(comparison.clone()).block(comparison_block),
+            chunks[1],
+        );
+    }
+}
+
+/// Saves how a run ended as a short tag, e.g. `solved` or `exit 1`.
+fn status_tag<S: Serializer>(status: &TaskStatus, serializer: S) -> Result<S::Ok, S::Error> {
+    let tag = match status {
+        TaskStatus::Finished(Some(status)) => match status.code() {
+            Some(code) => format!("exit {}", code),
+            None => exit_signal(status)
+                .map_or("unknown".to_string(), |signal| format!("signal {}", signal)),
+        },
+        TaskStatus::Finished(None) => "unknown".to_string(),
+        TaskStatus::BuildFailed => "build failed".to_string(),
+        TaskStatus::Solved => "solved".to_string(),
+        TaskStatus::Failed => "failed".to_string(),
+        TaskStatus::Cancelled => "cancelled".to_string(),
+        TaskStatus::Idle | TaskStatus::Building | TaskStatus::Running => "unknown".to_string(),
+    };
+    serializer.serialize_str(&tag)
+}
+
+fn from_status_tag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TaskStatus, D::Error> {
+    let tag = String::deserialize(deserializer)?;
+    let status = match tag.as_str() {
+        "build failed" => TaskStatus::BuildFailed,
+        "solved" => TaskStatus::Solved,
+        "failed" => TaskStatus::Failed,
+        "cancelled" => TaskStatus::Cancelled,
+        "unknown" => TaskStatus::Finished(None),
+        status => {
+            let number = status
+                .split_once(' ')
+                .and_then(|(kind, number)| Some((kind, number.parse().ok()?)));
+            match number {
+                Some((kind, number)) => TaskStatus::Finished(exit_status(kind, number)),
+                None => return Err(D::Error::custom(format!("unknown status `{}`", tag))),
+            }
+        }
+    };
+    Ok(status)
+}
+
+/// Saves the time a run finished as whole seconds since the Unix epoch.
+fn unix_seconds<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    serializer.serialize_u64(seconds)
+}
+
+fn from_unix_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+    Ok(UNIX_EPOCH + Duration::from_secs(u64::deserialize(deserializer)?))
+}
+
+/// Saves at most `MAX_OUTPUT_LINES` of a run's output.
+fn truncated_output<S: Serializer>(output: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&truncate_lines(output, MAX_OUTPUT_LINES))
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Rebuilds an exit status from its exit code or the signal that killed
+/// the task. Other platforms cannot build one, so the status is unknown.
+#[cfg(unix)]
+fn exit_status(kind: &str, number: i32) -> Option<ExitStatus> {
+    use std::os::unix::process::ExitStatusExt;
+    match kind {
+        "exit" => Some(ExitStatus::from_raw(number << 8)),
+        "signal" => Some(ExitStatus::from_raw(number)),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+fn exit_status(_kind: &str, _number: i32) -> Option<ExitStatus> {
+    None
+}
+
+fn truncate_lines(text: &str, max_lines: usize) -> String {
+    match text.match_indices('\n').nth(max_lines - 1) {
+        Some((index, _)) => text[..=index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Describes how long ago a run finished, e.g. "5m ago".
+fn format_age(now: SystemTime, then: SystemTime) -> String {
+    let seconds = now.duration_since(then).unwrap_or_default().as_secs();
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(task_path: &str, output: &str, status: TaskStatus) -> RunRecord {
+        RunRecord {
+            task_path: task_path.to_string(),
+            input_file: Some("input/example.txt".to_string()),
+            status,
+            stats: RunStats {
+                run_time: Some(Duration::from_millis(250)),
+                ..RunStats::default()
+            },
+            answer: Some("11".to_string()),
+            verdict: Some(Verdict::Wrong {
+                expected: "12".to_string(),
+            }),
+            output: output.to_string(),
+            finished_at: UNIX_EPOCH + Duration::from_secs(1_733_000_000),
+        }
+    }
+
+    #[test]
+    fn test_history_round_trips_through_json() {
+        let mut history = RunHistory::default();
+        history.push(record(
+            "day1/task1",
+            "\x1b[1mAnswer: 11\x1b[0m\n",
+            TaskStatus::Solved,
+        ));
+        history.push(record("day2/task1", "", TaskStatus::Cancelled));
+        #[cfg(unix)]
+        history.push(record(
+            "day2/task1",
+            "",
+            TaskStatus::Finished(exit_status("exit", 101)),
+        ));
+
+        let loaded = RunHistory::parse(&serde_json::to_string(&history.records()).unwrap());
+        let run = &loaded.runs("day1/task1")[0];
+        assert_eq!(run.output, "\x1b[1mAnswer: 11\x1b[0m\n");
+        assert_eq!(run.input_file.as_deref(), Some("input/example.txt"));
+        assert_eq!(run.stats.run_time, Some(Duration::from_millis(250)));
+        assert_eq!(
+            run.finished_at,
+            UNIX_EPOCH + Duration::from_secs(1_733_000_000)
+        );
+        assert!(run.status == TaskStatus::Solved);
+        assert!(matches!(&run.verdict, Some(Verdict::Wrong { expected }) if expected == "12"));
+
+        let statuses: Vec<Option<String>> = loaded
+            .runs("day2/task1")
+            .iter()
+            .map(|run| run.status.describe())
+            .collect();
+        assert_eq!(statuses[0].as_deref(), Some("cancelled"));
+        #[cfg(unix)]
+        assert_eq!(statuses[1].as_deref(), Some("exit code 101"));
+    }
+
+    #[test]
+    fn test_history_reads_what_earlier_versions_saved() {
+        let loaded = RunHistory::parse(
+            r#"[
+                {"task": "day1/task1", "input": null, "status": "exit 101", "build_time": 1.5,
+                 "parse_time": null, "run_time": 0.25, "peak_rss_kb": 2048, "answer": "11",
+                 "verdict": "correct", "output": "Answer: 11\n", "finished_at": 1733000000},
+                {"task": "day1/task1", "status": "not a status", "finished_at": 1733000000},
+                {"task": "day1/task1", "status": "solved", "finished_at": 1733000060}
+            ]"#,
+        );
+        let runs = loaded.runs("day1/task1");
+        assert_eq!(runs.len(), 2);
+        #[cfg(unix)]
+        assert_eq!(runs[0].status.describe().as_deref(), Some("exit code 101"));
+        assert_eq!(runs[0].stats.build_time, Some(Duration::from_millis(1500)));
+        assert_eq!(runs[0].stats.peak_rss_kb, Some(2048));
+        assert_eq!(runs[0].verdict, Some(Verdict::Correct));
+        assert_eq!(runs[1].output, "");
+        assert!(runs[1].status == TaskStatus::Solved);
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_compares_runs() {
+        let mut history = RunHistory::default();
+        for i in 0..MAX_RUNS_PER_TASK + 2 {
+            history.push(record(
+                "day1/task1",
+                &format!("run {}\n", i),
+                TaskStatus::Solved,
+            ));
+        }
+        let runs = history.runs("day1/task1");
+        assert_eq!(runs.len(), MAX_RUNS_PER_TASK);
+        assert_eq!(runs[0].output, "run 2\n");
+
+        history.select_previous("day1/task1");
+        history.toggle_mark("day1/task1");
+        history.select_next("day1/task1");
+        history.toggle_comparison("day1/task1", &KeyBindings::default());
+        let comparison: Vec<String> = history
+            .comparison
+            .iter()
+            .flatten()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            comparison,
+            vec![
+                "--- run #9",
+                "+++ run #10",
+                "@@ -1,1 +1,1 @@",
+                "-run 10",
+                "+run 11"
+            ]
+        );
+
+        // New runs do not move the selection or the mark off their runs.
+        history.push(record("day1/task2", "other\n", TaskStatus::Solved));
+        history.push(record("day1/task1", "run 12\n", TaskStatus::Cancelled));
+        let (number, run) = history.selected_run("day1/task1").unwrap();
+        assert_eq!((number, run.output.as_str()), (9, "run 11\n"));
+        assert_eq!(history.marked, Some(7));
+        assert!(history.runs("day1/task1")[9].status == TaskStatus::Cancelled);
+        assert_eq!(truncate_lines("a\nb\nc\n", 2), "a\nb\n");
+    }
+
+    #[test]
+    fn test_selection_on_a_dropped_run_moves_to_the_oldest() {
+        let mut history = RunHistory::default();
+        for i in 0..MAX_RUNS_PER_TASK {
+            history.push(record(
+                "day1/task1",
+                &format!("run {}\n", i),
+                TaskStatus::Solved,
+            ));
+        }
+        for _ in 0..MAX_RUNS_PER_TASK {
+            history.select_previous("day1/task1");
+        }
+        history.select_next("day1/task1");
+        history.toggle_mark("day1/task1");
+        history.select_previous("day1/task1");
+        history.toggle_comparison("day1/task1", &KeyBindings::default());
+        assert!(history.comparison.is_some());
+
+        history.push(record("day1/task1", "run 10\n", TaskStatus::Solved));
+        let (number, run) = history.selected_run("day1/task1").unwrap();
+        assert_eq!((number, run.output.as_str()), (1, "run 1\n"));
+        assert_eq!(history.marked, Some(0));
+        assert!(history.comparison.is_none());
+
+        history.push(record("day1/task1", "run 11\n", TaskStatus::Solved));
+        let (number, run) = history.selected_run("day1/task1").unwrap();
+        assert_eq!((number, run.output.as_str()), (1, "run 2\n"));
+        assert_eq!(history.marked, None);
+    }
+}
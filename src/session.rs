@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Where the TUI keeps its state between launches, relative to the project
+/// directory.
+pub const SESSION_FILE: &str = ".aoc/session.json";
+
+/// What the TUI was showing when it was last closed, so that it can be shown
+/// again on the next launch. Days, tasks or inputs that have since gone away
+/// are skipped when it is restored.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// The identifier of the selected tree node, e.g. `["day1", "task2"]`.
+    pub selected: Vec<String>,
+    /// The identifiers of the open tree nodes, or `None` if they were never
+    /// saved and every day should be opened.
+    pub opened: Option<Vec<Vec<String>>>,
+    /// The name of the active preview tab, e.g. `source`.
+    pub active_tab: Option<String>,
+    pub scroll_offset: usize,
+    /// The input file last chosen for each task, e.g. `example.txt`.
+    pub last_inputs: HashMap<String, String>,
+}
+
+impl Session {
+    /// Reads the session saved on the last quit. A missing or unreadable
+    /// file gives the session of a first launch.
+    pub fn load() -> Session {
+        std::fs::read_to_string(SESSION_FILE)
+            .map(|contents| Session::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(dir) = Path::new(SESSION_FILE).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(SESSION_FILE, serde_json::to_string(self)?)
+    }
+
+    /// Reads a saved session. Fields it does not have keep their defaults,
+    /// and a session that cannot be read at all gives a first launch.
+    fn parse(contents: &str) -> Session {
+        serde_json::from_str(contents).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trips_through_json() {
+        let session = Session {
+            selected: vec!["day3".to_string(), "task2".to_string()],
+            opened: Some(vec![vec!["day3".to_string()]]),
+            active_tab: Some("source".to_string()),
+            scroll_offset: 42,
+            last_inputs: HashMap::from([("day3/task2".to_string(), "example.txt".to_string())]),
+        };
+        assert_eq!(
+            Session::parse(&serde_json::to_string(&session).unwrap()),
+            session
+        );
+    }
+
+    #[test]
+    fn test_session_tolerates_missing_fields() {
+        let session = Session::parse(r#"{"scroll_offset": 3, "opened": null}"#);
+        assert_eq!(
+            session,
+            Session {
+                scroll_offset: 3,
+                ..Session::default()
+            }
+        );
+        assert_eq!(
+            Session::parse(r#"{"selected": ["day1", 7]}"#),
+            Session::default()
+        );
+    }
+}
@@ -1,84 +1,378 @@
+use crate::answers::{self, Verdict};
+use crate::config::Theme;
+use crate::fuzzy;
+use crate::session::Session;
+
 use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders};
-use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use tui_tree_widget::{Tree, TreeItem, TreeState};
 
+/// The last day of the calendar, used to look for task directories.
+const LAST_DAY: u8 = 25;
+
+/// The latest verdict for each task and input file name that has been run
+/// this session.
+type Verdicts = HashMap<(String, String), Verdict>;
+
 pub struct TaskFinder {
     pub file_tree: Vec<TreeItem<'static, String>>,
     pub file_tree_state: TreeState<String>,
+    verdicts: Verdicts,
+    /// The tasks whose tests failed when last run.
+    failing_tests: HashSet<String>,
+    /// The title of each day's README, which the search matches along with
+    /// the day, task and input names.
+    day_titles: HashMap<u8, String>,
+    /// The search query the tree is filtered by, if any.
+    pub query: Option<String>,
+    /// Whether the search prompt is open, so that keys edit the query.
+    pub searching: bool,
+    /// The input file last chosen for each task, which the task runs with
+    /// when it is run without choosing one.
+    last_inputs: HashMap<String, String>,
+    /// The tasks registered in the `aoc` runner, once it has been built.
+    registered: Option<BTreeSet<(u8, u8)>>,
 }
 
 impl TaskFinder {
     pub fn new() -> TaskFinder {
-        let file_tree = Self::load_file_tree();
-        let mut file_tree_state = TreeState::default();
-        Self::open_all_day_tasks(&file_tree, &mut file_tree_state);
-        file_tree_state.select_first();
-        TaskFinder {
-            file_tree,
-            file_tree_state,
-        }
-    }
-
-    fn load_file_tree() -> Vec<TreeItem<'static, String>> {
-        let mut items = Vec::new();
-        let re = Regex::new(r"^day\d+$").unwrap();
-        let paths = match std::fs::read_dir(".") {
-            Ok(paths) => paths,
-            Err(_) => return items,
+        let mut task_finder = TaskFinder {
+            file_tree: Vec::new(),
+            file_tree_state: TreeState::default(),
+            verdicts: Verdicts::new(),
+            failing_tests: HashSet::new(),
+            day_titles: load_day_titles(),
+            query: None,
+            searching: false,
+            last_inputs: HashMap::new(),
+            registered: None,
         };
+        task_finder.rebuild();
+        Self::open_all_day_tasks(&task_finder.file_tree, &mut task_finder.file_tree_state);
+        task_finder.file_tree_state.select_first();
+        task_finder
+    }
 
-        // Find all directories in the current directory that match the regex
-        let filtered_paths = paths
-            .filter(|p| p.is_ok())
-            .filter(|p| p.as_ref().unwrap().path().is_dir())
-            .filter(|p| {
-                let binding = p.as_ref().unwrap().path();
-                let dir_name = binding.file_name().and_then(|n| n.to_str());
-                dir_name.is_some() && re.is_match(dir_name.unwrap())
-            });
+    /// Restores the open nodes, selection and chosen inputs of an earlier
+    /// session. Anything that no longer exists is skipped, and a selection
+    /// that has gone falls back to its closest remaining ancestor.
+    pub fn restore(&mut self, session: &Session) {
+        self.last_inputs = session
+            .last_inputs
+            .iter()
+            .filter(|(task_path, input)| input_files(task_path).contains(input))
+            .map(|(task_path, input)| (task_path.clone(), input.clone()))
+            .collect();
+        self.rebuild();
 
-        // Sort the paths numerically by day
-        let mut paths: Vec<_> = filtered_paths.collect();
-        paths.sort_by(|a, b| {
-            let a = a.as_ref().unwrap().path();
-            let b = b.as_ref().unwrap().path();
-            let a = a.file_name().and_then(|n| n.to_str()).unwrap();
-            let b = b.file_name().and_then(|n| n.to_str()).unwrap();
-            let a = a.trim_start_matches("day").parse::<u32>().unwrap();
-            let b = b.trim_start_matches("day").parse::<u32>().unwrap();
-            a.cmp(&b)
-        });
+        if let Some(opened) = &session.opened {
+            self.file_tree_state.close_all();
+            for identifier in opened {
+                if self.contains(identifier) {
+                    self.file_tree_state.open(identifier.clone());
+                }
+            }
+        }
+        let selected = &session.selected;
+        if let Some(depth) = (1..=selected.len())
+            .rev()
+            .find(|&depth| self.contains(&selected[..depth]))
+        {
+            for ancestor in 1..depth {
+                self.file_tree_state.open(selected[..ancestor].to_vec());
+            }
+            self.file_tree_state.select(selected[..depth].to_vec());
+            self.scroll_to_selected();
+        }
+    }
+
+    /// Records the open nodes, selection and chosen inputs in a session.
+    pub fn save(&self, session: &mut Session) {
+        let mut opened: Vec<Vec<String>> = self.file_tree_state.opened().iter().cloned().collect();
+        opened.sort();
+        session.opened = Some(opened);
+        session.selected = self.file_tree_state.selected().to_vec();
+        session.last_inputs = self.last_inputs.clone();
+    }
+
+    /// Whether the tree has a node with the identifier.
+    fn contains(&self, identifier: &[String]) -> bool {
+        let mut items = self.file_tree.as_slice();
+        for id in identifier {
+            match items.iter().find(|item| item.identifier() == id) {
+                Some(item) => items = item.children(),
+                None => return false,
+            }
+        }
+        !identifier.is_empty()
+    }
+
+    /// Picks the input file to run a task with. An input chosen in the tree
+    /// is remembered for the task, and running the task without choosing one
+    /// uses the one chosen last.
+    pub fn choose_input(&mut self, task_path: &str, input_file: Option<String>) -> Option<String> {
+        let Some(input_file) = input_file else {
+            return self
+                .last_inputs
+                .get(task_path)
+                .map(|input| format!("input/{}", input));
+        };
+        let input_name = answers::input_name(Some(&input_file));
+        if input_name == answers::input_name(None) {
+            self.last_inputs.remove(task_path);
+        } else {
+            self.last_inputs.insert(task_path.to_string(), input_name);
+        }
+        self.rebuild();
+        Some(input_file)
+    }
+
+    /// Records the verdict of a run and rebuilds the tree to show its badge.
+    pub fn set_verdict(&mut self, task_path: &str, input_name: &str, verdict: Verdict) {
+        self.verdicts
+            .insert((task_path.to_string(), input_name.to_string()), verdict);
+        self.rebuild();
+    }
 
-        for path in paths {
-            let path = path.as_ref().unwrap().path();
-            let dir_name = path.file_name().unwrap().to_str().unwrap();
-            let dir_item = TreeItem::new(dir_name.to_string(), dir_name.to_string(), vec![]);
-            if let Ok(mut dir_item) = dir_item {
-                Self::add_task_items(&path, &mut dir_item);
-                items.push(dir_item);
+    /// Records which tasks are registered in the `aoc` runner and rebuilds the
+    /// tree if that changed, so that the others are marked.
+    pub fn set_registered(&mut self, registered: Option<BTreeSet<(u8, u8)>>) {
+        if registered != self.registered {
+            self.registered = registered;
+            self.rebuild();
+        }
+    }
+
+    /// Records whether each task's tests passed and rebuilds the tree, so
+    /// that tasks with failing tests and their days are shown in red.
+    pub fn set_test_results(&mut self, results: &[(String, bool)]) {
+        for (task_path, passed) in results {
+            if *passed {
+                self.failing_tests.remove(task_path);
+            } else {
+                self.failing_tests.insert(task_path.clone());
             }
         }
+        self.rebuild();
+    }
+
+    /// Opens the search prompt with an empty query.
+    pub fn start_search(&mut self) {
+        self.searching = true;
+        self.query = Some(String::new());
+    }
+
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(query) = self.query.as_mut() {
+            query.push(c);
+            self.update_search();
+        }
+    }
+
+    pub fn pop_search_char(&mut self) {
+        if let Some(query) = self.query.as_mut() {
+            query.pop();
+            self.update_search();
+        }
+    }
+
+    /// Filters the tree by the query and selects the best match.
+    fn update_search(&mut self) {
+        let Some(best_match) = self.rebuild() else {
+            return;
+        };
+        for depth in 1..best_match.len() {
+            self.file_tree_state.open(best_match[..depth].to_vec());
+        }
+        self.file_tree_state.select(best_match);
+        self.scroll_to_selected();
+    }
+
+    /// Closes the search prompt, keeping the tree filtered.
+    pub fn confirm_search(&mut self) {
+        self.searching = false;
+    }
 
-        items
+    /// Clears the query and shows the full tree again. The selected item is
+    /// still in the full tree, so it stays selected.
+    pub fn clear_search(&mut self) {
+        self.searching = false;
+        self.query = None;
+        self.rebuild();
+        self.scroll_to_selected();
     }
 
-    fn add_task_items(path: &Path, parent: &mut TreeItem<'static, String>) {
-        let tasks = ["task1", "task2"];
-        for task in tasks.iter() {
-            let task_path = path.join(task);
-            if task_path.is_dir() {
-                let task_item = TreeItem::new(task.to_string(), task.to_string(), vec![]);
-                if let Ok(task_item) = task_item {
-                    match parent.add_child(task_item) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            println!("Error adding task");
-                        }
-                    }
+    /// Scrolls back to the top before bringing the selection into view, as
+    /// the tree may have far fewer rows than when it was last scrolled.
+    fn scroll_to_selected(&mut self) {
+        self.file_tree_state.scroll_up(usize::MAX);
+        self.file_tree_state.scroll_selected_into_view();
+    }
+
+    /// Rebuilds the tree with a node for each of `all_tasks`, keeping only
+    /// the days, tasks and inputs that match the search query. A day or task
+    /// that matches keeps everything under it. The tree state refers to items
+    /// by identifier, so the selection and open days are kept.
+    ///
+    /// Returns the identifier of the best match for a non-empty query.
+    fn rebuild(&mut self) -> Option<Vec<String>> {
+        let mut tasks: BTreeMap<u8, BTreeSet<u8>> = BTreeMap::new();
+        for (day, part) in all_tasks() {
+            tasks.entry(day).or_default().insert(part);
+        }
+
+        let query = self.query.clone().unwrap_or_default();
+        let mut best_match: Option<(i64, Vec<String>)> = None;
+        let mut consider = |score: Option<i64>, identifier: &[&str]| {
+            let Some(score) = score else {
+                return false;
+            };
+            if !query.trim().is_empty() && best_match.as_ref().is_none_or(|(best, _)| score > *best)
+            {
+                let identifier = identifier.iter().map(|id| id.to_string()).collect();
+                best_match = Some((score, identifier));
+            }
+            true
+        };
+
+        let mut file_tree = Vec::new();
+        for (day, parts) in tasks {
+            let day_name = format!("day{}", day);
+            let title = self.day_titles.get(&day).map(String::as_str).unwrap_or("");
+            let day_score = fuzzy::score(&query, &day_name).max(fuzzy::score(&query, title));
+            let day_matches = consider(day_score, &[&day_name]);
+
+            let mut task_items = Vec::new();
+            for part in parts {
+                let task_path = task_path(day, part);
+                let task_name = format!("task{}", part);
+                let task_matches =
+                    consider(fuzzy::score(&query, &task_path), &[&day_name, &task_name]);
+                let inputs: Vec<String> = input_files(&task_path)
+                    .into_iter()
+                    .filter(|input| {
+                        let score = fuzzy::score(&query, &format!("{}/{}", task_path, input));
+                        consider(score, &[&day_name, &task_name, input])
+                            || day_matches
+                            || task_matches
+                    })
+                    .collect();
+                if day_matches || task_matches || !inputs.is_empty() {
+                    task_items.extend(self.task_item(day, part, inputs));
                 }
             }
+
+            if task_items.is_empty() {
+                continue;
+            }
+            let failing = task_items.iter().any(|task| {
+                self.failing_tests
+                    .contains(&format!("{}/{}", day_name, task.identifier()))
+            });
+            let label = Line::styled(day_name.clone(), Self::test_style(failing));
+            file_tree.extend(TreeItem::new(day_name, label, task_items).ok());
+        }
+
+        self.file_tree = file_tree;
+        best_match.map(|(_, identifier)| identifier)
+    }
+
+    /// Builds the tree item for a task, marking tasks that are not in the
+    /// registry since they are built and run with cargo instead. Nothing is
+    /// marked until the runner has been built and listed its solutions. The badge
+    /// next to the task is the verdict for its default input.
+    fn task_item(
+        &self,
+        day: u8,
+        part: u8,
+        inputs: Vec<String>,
+    ) -> Option<TreeItem<'static, String>> {
+        let task_path = task_path(day, part);
+        let task_name = format!("task{}", part);
+        let text = match &self.registered {
+            Some(registered) if !registered.contains(&(day, part)) => {
+                format!("{} (cargo)", task_name)
+            }
+            _ => task_name.clone(),
+        };
+        let verdict = self
+            .verdicts
+            .get(&(task_path.clone(), answers::input_name(None)));
+        let mut label = Self::label(text, verdict)
+            .style(Self::test_style(self.failing_tests.contains(&task_path)));
+        if let Some(input) = self.last_inputs.get(&task_path) {
+            label.push_span(Span::styled(format!(" [{}]", input), Color::DarkGray));
+        }
+        let input_items = self.input_items(&task_path, inputs);
+        TreeItem::new(task_name, label, input_items).ok()
+    }
+
+    /// Adds the verdict badge, if any, to a tree item's text.
+    fn label(text: String, verdict: Option<&Verdict>) -> Line<'static> {
+        let mut spans = vec![Span::raw(text)];
+        spans.extend(verdict.map(Verdict::badge));
+        Line::from(spans)
+    }
+
+    fn test_style(failing: bool) -> Style {
+        if failing {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Lists the task's input files so that a specific input can be chosen
+    /// to run the task with.
+    fn input_items(&self, task_path: &str, inputs: Vec<String>) -> Vec<TreeItem<'static, String>> {
+        inputs
+            .into_iter()
+            .map(|name| {
+                let verdict = self.verdicts.get(&(task_path.to_string(), name.clone()));
+                TreeItem::new_leaf(name.clone(), Self::label(name, verdict))
+            })
+            .collect()
+    }
+
+    /// Returns the selected task directory, e.g. `day1/task1`, along with the
+    /// input file relative to it if an input node is selected.
+    pub fn selected_task(&self) -> Option<(String, Option<String>)> {
+        match self.file_tree_state.selected() {
+            [day, task] => Some((format!("{}/{}", day, task), None)),
+            [day, task, input] => Some((
+                format!("{}/{}", day, task),
+                Some(format!("input/{}", input)),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the directory of the selected day or task, e.g. `day1` or
+    /// `day1/task2`. An input file counts as its task.
+    pub fn selected_path(&self) -> Option<String> {
+        match self.file_tree_state.selected() {
+            [day] => Some(day.clone()),
+            [day, task, ..] => Some(format!("{}/{}", day, task)),
+            [] => None,
+        }
+    }
+
+    /// Returns the task directories to test for the selection: the selected
+    /// task, or every task of the selected day.
+    pub fn selected_test_paths(&self) -> Vec<String> {
+        match self.file_tree_state.selected() {
+            [day] => self
+                .file_tree
+                .iter()
+                .filter(|item| item.identifier() == day)
+                .flat_map(|item| item.children())
+                .map(|task| format!("{}/{}", day, task.identifier()))
+                .collect(),
+            [day, task, ..] => vec![format!("{}/{}", day, task)],
+            _ => Vec::new(),
         }
     }
 
@@ -92,22 +386,93 @@ impl TaskFinder {
         });
     }
 
-    pub fn render<B: ratatui::backend::Backend>(
-        &mut self,
-        f: &mut ratatui::Frame,
-        area: ratatui::layout::Rect,
-    ) {
+    pub fn render(&mut self, f: &mut ratatui::Frame, area: ratatui::layout::Rect, theme: Theme) {
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.secondary))
+            .title("Days");
+        if let Some(query) = &self.query {
+            let prompt = if self.searching {
+                format!("/{}_", query)
+            } else {
+                format!("/{} (esc: clear)", query)
+            };
+            block = block.title_bottom(prompt);
+        }
+
         let binding = self.file_tree.clone();
         let file_tree = Tree::new(&binding)
             .unwrap()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Red))
-                    .title("Days"),
-            )
-            .highlight_style(Style::default().fg(Color::Green))
+            .block(block)
+            .highlight_style(Style::default().fg(theme.highlight))
             .highlight_symbol(">> ");
         f.render_stateful_widget(file_tree, area, &mut self.file_tree_state);
     }
 }
+
+/// Lists every `dayN/taskM` directory as `(day, part)` in order, whether its
+/// task is in the solution registry or is still run through cargo.
+pub fn all_tasks() -> Vec<(u8, u8)> {
+    let mut tasks = Vec::new();
+    for day in 1..=LAST_DAY {
+        for part in [1, 2] {
+            if Path::new(&task_path(day, part)).is_dir() {
+                tasks.push((day, part));
+            }
+        }
+    }
+    tasks
+}
+
+/// Lists the files in the task's `input` directory, sorted by name.
+pub fn input_files(task_path: &str) -> Vec<String> {
+    let entries = match std::fs::read_dir(Path::new(task_path).join("input")) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut file_names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().to_str().map(|n| n.to_string()))
+        .collect();
+    file_names.sort();
+    file_names
+}
+
+/// Reads the first heading of each day's README, e.g. "Day 1".
+fn load_day_titles() -> HashMap<u8, String> {
+    (1..=LAST_DAY)
+        .filter_map(|day| {
+            let readme = std::fs::read_to_string(format!("day{}/README.md", day)).ok()?;
+            let title = readme.lines().find_map(|line| line.strip_prefix("# "))?;
+            Some((day, title.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Returns the directory of a task, e.g. `day1/task2`.
+pub fn task_path(day: u8, part: u8) -> String {
+    format!("day{}/task{}", day, part)
+}
+
+/// Splits a task directory such as `day1/task2` into its day and part.
+pub fn parse_task_path(task_path: &str) -> Option<(u8, u8)> {
+    let (day, task) = task_path.split_once('/')?;
+    let day = day.strip_prefix("day")?.parse().ok()?;
+    let part = task.strip_prefix("task")?.parse().ok()?;
+    Some((day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_task_path() {
+        assert_eq!(parse_task_path("day12/task2"), Some((12, 2)));
+        assert_eq!(parse_task_path(&task_path(3, 1)), Some((3, 1)));
+        assert_eq!(parse_task_path("day1"), None);
+        assert_eq!(parse_task_path("src/task1"), None);
+    }
+}
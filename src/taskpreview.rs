@@ -1,32 +1,288 @@
-use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Style};
-use ratatui::symbols::line;
-use ratatui::widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarState};
-use strip_ansi_escapes::strip;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt};
-use tokio::process::Command;
+use crate::ansi::AnsiParser;
+use crate::answers::{self, Answers, Verdict};
+use crate::buildmessages::{BuildMessage, Diagnostic};
+use crate::config::{Action, KeyBindings, Theme};
+use crate::diff;
+use crate::highlight;
+use crate::linelayout::LineLayout;
+use crate::markdown;
+use crate::outputsearch::{self, OutputSearch};
+use crate::runhistory::RunRecord;
+use crate::taskrunner::{self, describe_exit_status, RunMode, RunStats, TaskHandle, TaskOutput};
+use crate::testreport::TestReport;
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarState, Wrap};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::error::TryRecvError;
+
+/// How many stderr lines or compiler errors are included in the error popup
+/// when a task fails.
+const ERROR_CONTEXT_LINES: usize = 5;
+
+/// How many lines or columns a notch of the mouse wheel scrolls.
+const MOUSE_SCROLL_LINES: isize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TaskStatus {
+    Idle,
+    Building,
+    BuildFailed,
+    Running,
+    Finished(Option<ExitStatus>),
+    /// A registered solution returned its answer.
+    Solved,
+    /// A registered solution panicked, could not read its input or gave no
+    /// answer.
+    Failed,
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// Describes the status for display, or `None` when idle.
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            TaskStatus::Idle => None,
+            TaskStatus::Building => Some("building".to_string()),
+            TaskStatus::BuildFailed => Some("build failed".to_string()),
+            TaskStatus::Running => Some("running".to_string()),
+            TaskStatus::Finished(Some(status)) => Some(describe_exit_status(status)),
+            TaskStatus::Finished(None) => Some("unknown exit status".to_string()),
+            TaskStatus::Solved => Some("solved".to_string()),
+            TaskStatus::Failed => Some("failed".to_string()),
+            TaskStatus::Cancelled => Some("cancelled".to_string()),
+        }
+    }
+
+    /// Whether the task ran to the end without failing.
+    pub fn succeeded(&self) -> bool {
+        match self {
+            TaskStatus::Finished(Some(status)) => status.success(),
+            TaskStatus::Solved => true,
+            _ => false,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            status if status.succeeded() => Color::Green,
+            TaskStatus::Building | TaskStatus::Running => Color::Yellow,
+            _ => Color::Red,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PreviewTab {
+    /// The README of the selected day or the description of the selected
+    /// task.
+    Docs,
+    /// The source of the selected task, highlighted.
+    Source,
+    /// What changed between task1 and task2 of the selected day.
+    Diff,
+    Build,
+    Stdout,
+    Stderr,
+    Tests,
+}
+
+impl PreviewTab {
+    const ALL: [PreviewTab; 7] = [
+        PreviewTab::Docs,
+        PreviewTab::Source,
+        PreviewTab::Diff,
+        PreviewTab::Build,
+        PreviewTab::Stdout,
+        PreviewTab::Stderr,
+        PreviewTab::Tests,
+    ];
+
+    /// The tab's name as shown in the title, e.g. `source`.
+    pub fn name(self) -> &'static str {
+        match self {
+            PreviewTab::Docs => "docs",
+            PreviewTab::Source => "source",
+            PreviewTab::Diff => "diff",
+            PreviewTab::Build => "build",
+            PreviewTab::Stdout => "stdout",
+            PreviewTab::Stderr => "stderr",
+            PreviewTab::Tests => "tests",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PreviewTab> {
+        PreviewTab::ALL.into_iter().find(|tab| tab.name() == name)
+    }
+}
+
+/// The output of one stream of a task, kept both with ANSI styling applied
+/// and with escape sequences stripped.
+#[derive(Default)]
+pub struct OutputBuffer {
+    pub raw: String,
+    pub styled_lines: Vec<Line<'static>>,
+    pub plain_lines: Vec<String>,
+    ansi_parser: AnsiParser,
+}
+
+impl OutputBuffer {
+    pub fn push_line(&mut self, line: &str) {
+        self.raw.push_str(line);
+        self.raw.push('\n');
+        self.styled_lines.push(self.ansi_parser.parse_line(line));
+        self.plain_lines.push(strip_ansi_escapes::strip_str(line));
+    }
+
+    pub fn len(&self) -> usize {
+        self.plain_lines.len()
+    }
+
+    pub fn clear(&mut self) {
+        *self = OutputBuffer::default();
+    }
+}
+
+/// Counts of the crates cargo has reported while building a task.
+#[derive(Default)]
+pub struct BuildProgress {
+    pub compiled: usize,
+    pub fresh: usize,
+    pub errors: Vec<Diagnostic>,
+    pub warnings: usize,
+}
+
+impl BuildProgress {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} compiled, {} fresh", self.compiled, self.fresh);
+        if self.warnings > 0 {
+            summary.push_str(&format!(", {} warnings", self.warnings));
+        }
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} errors", self.errors.len()));
+        }
+        summary
+    }
+}
+
+/// A `cargo test` run over one or more tasks, which are tested one at a
+/// time.
+struct TestRun {
+    handle: TaskHandle,
+    /// The task being tested.
+    task_path: String,
+    pending: VecDeque<String>,
+    /// Whether each finished task's tests passed.
+    results: Vec<(String, bool)>,
+    /// The exit status of the first task whose tests failed, or else of the
+    /// last task.
+    status: Option<ExitStatus>,
+    all_passed: bool,
+    run_time: Duration,
+}
 
 pub struct TaskPreview {
-    pub file_preview: String,
-    pub scroll_offset: usize,
-    pub total_lines: usize,
-    pub scrollbar_state: ScrollbarState,
+    pub file_preview: OutputBuffer,
+    pub stderr_preview: OutputBuffer,
+    pub build_preview: OutputBuffer,
+    pub build_progress: BuildProgress,
+    pub active_tab: PreviewTab,
+    /// The first visual line shown, counting wrapped lines separately.
+    scroll_offset: usize,
+    /// The first column shown when lines are not wrapped.
+    horizontal_offset: usize,
+    /// Whether long lines are wrapped, or cut off and scrolled horizontally.
+    wrap: bool,
+    /// Where the lines of the active tab start once wrapped, and the size of
+    /// the area showing them, as of the last render.
+    layout: LineLayout,
+    /// Changes whenever the lines shown change other than by output being
+    /// added, so that they are measured again.
+    content_version: u64,
+    viewport: Rect,
+    scrollbar_area: Rect,
+    pub status: TaskStatus,
+    pub stats: Option<RunStats>,
+    pub show_colours: bool,
+    pub task_path: Option<String>,
+    /// The input file the task was run with, if not its default.
+    pub input_file: Option<String>,
+    /// How the last run's answer compared with the task's answers file.
+    pub verdict: Option<Verdict>,
+    /// The number of the run in the history, if an earlier run is shown.
+    reopened_run: Option<usize>,
+    /// How the task shown was run, until an earlier run is reopened.
+    run_mode: Option<RunMode>,
+    pub test_report: TestReport,
+    /// The search through the output in the active tab, if any.
+    output_search: Option<OutputSearch>,
+    /// A line of the output to scroll to on the next render, once it is known
+    /// how the lines before it wrap.
+    scroll_target: Option<usize>,
+    /// The day or task directory selected in the tree, whose docs and source
+    /// are shown in the docs and source tabs.
+    selected_path: Option<String>,
+    docs: Vec<Line<'static>>,
+    /// The source file of the selected task, if a task is selected.
+    pub source_file: Option<String>,
+    source: Vec<Line<'static>>,
+    diff: Vec<Line<'static>>,
+    task: Option<TaskHandle>,
+    tests: Option<TestRun>,
 }
 
 impl TaskPreview {
-    pub fn new() -> TaskPreview {
+    pub fn new(keys: &KeyBindings) -> TaskPreview {
+        let mut file_preview = OutputBuffer::default();
+        file_preview.push_line(&keys.describe(&[Action::Run], "run the selected task"));
         TaskPreview {
-            file_preview: "Press Enter to execute a task".to_string(),
+            file_preview,
+            stderr_preview: OutputBuffer::default(),
+            build_preview: OutputBuffer::default(),
+            build_progress: BuildProgress::default(),
+            active_tab: PreviewTab::Docs,
             scroll_offset: 0,
-            total_lines: 0,
-            scrollbar_state: ScrollbarState::default(),
+            horizontal_offset: 0,
+            wrap: true,
+            layout: LineLayout::default(),
+            content_version: 0,
+            viewport: Rect::default(),
+            scrollbar_area: Rect::default(),
+            status: TaskStatus::Idle,
+            stats: None,
+            show_colours: true,
+            task_path: None,
+            input_file: None,
+            verdict: None,
+            reopened_run: None,
+            run_mode: None,
+            test_report: TestReport::default(),
+            output_search: None,
+            scroll_target: None,
+            selected_path: None,
+            docs: load_docs(None),
+            source_file: None,
+            source: Vec::new(),
+            diff: load_diff(None),
+            task: None,
+            tests: None,
         }
     }
 
-    pub fn render<B: ratatui::backend::Backend>(
+    pub fn render(
         &mut self,
         f: &mut ratatui::Frame,
         area: ratatui::layout::Rect,
+        keys: &KeyBindings,
+        theme: Theme,
     ) {
         let preview_chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -35,50 +291,901 @@ impl TaskPreview {
 
         let file_preview_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green))
-            .title("Preview");
+            .border_style(Style::default().fg(theme.primary))
+            .title(self.title());
 
-        let file_preview = Paragraph::new(self.file_preview.as_str())
-            .block(file_preview_block)
-            .scroll((self.scroll_offset.saturating_sub(10).try_into().unwrap(), 0));
+        self.update_output_search();
 
+        // Measure the text for the area inside the borders, so that scrolling
+        // works on the lines as they are shown rather than as they were read.
+        self.viewport = file_preview_block.inner(preview_chunks[0]);
+        self.scrollbar_area = preview_chunks[1];
+        self.measure(keys);
+        if let Some(line) = self.scroll_target.take() {
+            self.scroll_offset = self
+                .layout
+                .start_of(line)
+                .saturating_sub(usize::from(self.viewport.height) / 3);
+        }
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+        self.horizontal_offset = self.horizontal_offset.min(self.max_horizontal_offset());
+
+        // Only the lines in view are handed to the paragraph, scrolled by
+        // however much of the first one is above the top.
+        let first = self.layout.line_at(self.scroll_offset);
+        let last = self
+            .layout
+            .line_at(self.scroll_offset + usize::from(self.viewport.height));
+        let lines = self.visible_lines(keys, first..last + 1);
+        let mut file_preview = Paragraph::new(lines);
+        if self.wrap {
+            file_preview = file_preview.wrap(Wrap { trim: false });
+        }
+        let skipped = self.scroll_offset - self.layout.start_of(first);
+        let horizontal_offset = if self.wrap { 0 } else { self.horizontal_offset };
+        let file_preview = file_preview.block(file_preview_block).scroll((
+            skipped.try_into().unwrap_or(u16::MAX),
+            horizontal_offset.try_into().unwrap_or(u16::MAX),
+        ));
         f.render_widget(file_preview, preview_chunks[0]);
 
-        let scrollbar = Scrollbar::default().style(Style::default().fg(Color::Green));
-        f.render_stateful_widget(scrollbar, preview_chunks[1], &mut self.scrollbar_state);
+        // The scrollbar's positions are the offsets the view can be scrolled
+        // to, and its thumb covers the share of the lines that fit in view.
+        let mut scrollbar_state = ScrollbarState::new(self.max_scroll_offset() + 1)
+            .viewport_content_length(self.viewport.height.into())
+            .position(self.scroll_offset);
+        let scrollbar = Scrollbar::default().style(Style::default().fg(theme.primary));
+        f.render_stateful_widget(scrollbar, preview_chunks[1], &mut scrollbar_state);
     }
 
-    pub async fn run_task(&mut self, task_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let full_path = std::env::current_dir().unwrap().join(task_path);
+    /// Measures any lines of the active tab that have not been measured for
+    /// the preview's current width.
+    fn measure(&mut self, keys: &KeyBindings) {
+        let mut layout = std::mem::take(&mut self.layout);
+        let (version, width, wrap) = (self.content_version, self.viewport.width, self.wrap);
+        match self.active_buffer() {
+            Some(buffer) => layout.update(version, width, wrap, &buffer.plain_lines),
+            None => layout.update(version, width, wrap, &self.generated_lines(keys)),
+        }
+        self.layout = layout;
+    }
 
-        self.file_preview.clear();
+    /// Styles the lines of the active tab in a range, with the current match
+    /// of any search highlighted. The range is cut short at the last line.
+    fn visible_lines(&self, keys: &KeyBindings, range: Range<usize>) -> Vec<Line<'static>> {
+        let first = range.start;
+        let mut lines = match self.active_buffer() {
+            Some(buffer) => {
+                let range = first.min(buffer.len())..range.end.min(buffer.len());
+                if self.show_colours {
+                    buffer.styled_lines[range].to_vec()
+                } else {
+                    buffer.plain_lines[range]
+                        .iter()
+                        .cloned()
+                        .map(Line::from)
+                        .collect()
+                }
+            }
+            None => {
+                let generated = self.generated_lines(keys);
+                let range = first.min(generated.len())..range.end.min(generated.len());
+                generated[range].to_vec()
+            }
+        };
+        if let Some(search) = &self.output_search {
+            for m in search.matches.iter().filter(|m| m.index == 0) {
+                if let Some(line) = m.line.checked_sub(first).and_then(|i| lines.get_mut(i)) {
+                    *line = search.highlight(m.line, std::mem::take(line));
+                }
+            }
+        }
+        lines
+    }
+
+    fn max_scroll_offset(&self) -> usize {
+        self.layout
+            .total()
+            .saturating_sub(self.viewport.height.into())
+    }
+
+    fn max_horizontal_offset(&self) -> usize {
+        self.layout
+            .widest()
+            .saturating_sub(self.viewport.width.into())
+    }
+
+    /// Has the lines shown measured again on the next render.
+    fn content_changed(&mut self) {
+        self.content_version += 1;
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Shows a tab scrolled to where it was in an earlier session. The offset
+    /// is clamped to the tab's length when it is next rendered.
+    pub fn restore_view(&mut self, tab: PreviewTab, scroll_offset: usize) {
+        self.show_tab(tab);
+        self.scroll_offset = scroll_offset;
+    }
+
+    /// Scrolls up or down by a number of visual lines.
+    pub fn scroll_by(&mut self, lines: isize) {
+        self.scroll_offset = self
+            .scroll_offset
+            .saturating_add_signed(lines)
+            .min(self.max_scroll_offset());
+    }
+
+    /// Scrolls up or down by a number of pages, keeping a line of the
+    /// previous page in view.
+    pub fn scroll_pages(&mut self, pages: isize) {
+        let page = (self.viewport.height as isize - 1).max(1);
+        self.scroll_by(pages * page);
+    }
+
+    pub fn scroll_to_top(&mut self) {
         self.scroll_offset = 0;
-        self.total_lines = 0;
-
-        let mut command = Command::new("cargo")
-            .arg("run")
-            .arg("--quiet")
-            .current_dir(full_path)
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::null())
-            .spawn()
-            .expect("Failed to start task");
-
-        let stdout = command.stdout.take().expect("Failed to open stdout");
-        let mut buffer = Vec::new();
-        let mut reader = tokio::io::BufReader::new(stdout);
-        reader.read_to_end(&mut buffer).await?;
-        let colored_text = String::from_utf8_lossy(&buffer);
-
-        let lines = colored_text.lines().count();
-        self.file_preview.push_str(&colored_text);
-        self.total_lines = lines;
-        self.scrollbar_state = ScrollbarState::new(self.total_lines).position(self.scroll_offset);
-
-        let output = command.wait().await;
-        match output {
-            Ok(_) => Ok(()),
-            Err(e) => Err(Box::new(e)),
+        self.horizontal_offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = self.max_scroll_offset();
+    }
+
+    /// Scrolls left or right by a number of columns. This only applies when
+    /// lines are not wrapped.
+    pub fn scroll_horizontally(&mut self, columns: isize) {
+        if !self.wrap {
+            self.horizontal_offset = self
+                .horizontal_offset
+                .saturating_add_signed(columns)
+                .min(self.max_horizontal_offset());
+        }
+    }
+
+    /// Switches between wrapping long lines and cutting them off, which
+    /// keeps wide output such as day 14's robot map readable.
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.horizontal_offset = 0;
+    }
+
+    /// Scrolls with the mouse wheel over the preview, and jumps to the
+    /// clicked or dragged position on the scrollbar.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let preview = self.viewport.union(self.scrollbar_area);
+        if !preview.contains((mouse.column, mouse.row).into()) {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(MOUSE_SCROLL_LINES),
+            MouseEventKind::ScrollUp => self.scroll_by(-MOUSE_SCROLL_LINES),
+            MouseEventKind::ScrollRight => self.scroll_horizontally(MOUSE_SCROLL_LINES),
+            MouseEventKind::ScrollLeft => self.scroll_horizontally(-MOUSE_SCROLL_LINES),
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
+                if mouse.column == self.scrollbar_area.x =>
+            {
+                let track = self.scrollbar_area.height.saturating_sub(1).max(1) as usize;
+                let row = mouse.row.saturating_sub(self.scrollbar_area.y) as usize;
+                self.scroll_offset = (row * self.max_scroll_offset()).div_ceil(track);
+                self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the active tab shows a task's output, which can be searched.
+    pub fn shows_output(&self) -> bool {
+        self.active_buffer().is_some()
+    }
+
+    pub fn start_output_search(&mut self) {
+        if self.shows_output() {
+            self.output_search = Some(OutputSearch::new());
+        }
+    }
+
+    /// Whether the output search query is being typed.
+    pub fn editing_output_search(&self) -> bool {
+        self.output_search
+            .as_ref()
+            .is_some_and(|search| search.editing)
+    }
+
+    pub fn has_output_search(&self) -> bool {
+        self.output_search.is_some()
+    }
+
+    pub fn push_output_search_char(&mut self, c: char) {
+        self.edit_output_search(|query| query.push(c));
+    }
+
+    pub fn pop_output_search_char(&mut self) {
+        self.edit_output_search(|query| {
+            query.pop();
+        });
+    }
+
+    /// Changes the query and moves to the first match from the top of the
+    /// view, like an incremental search in a pager.
+    fn edit_output_search(&mut self, edit: impl FnOnce(&mut String)) {
+        let top_line = self.top_line();
+        let Some(search) = self.output_search.as_mut() else {
+            return;
+        };
+        let mut query = std::mem::take(&mut search.query);
+        edit(&mut query);
+        search.set_query(query);
+        self.update_output_search();
+        if let Some(search) = self.output_search.as_mut() {
+            search.select_from(top_line);
+        }
+        self.scroll_to_match();
+    }
+
+    /// Stops typing the query, keeping the matches highlighted for `n` and
+    /// `N`.
+    pub fn confirm_output_search(&mut self) {
+        if let Some(search) = self.output_search.as_mut() {
+            search.editing = false;
+        }
+    }
+
+    pub fn clear_output_search(&mut self) {
+        self.output_search = None;
+    }
+
+    pub fn next_match(&mut self) {
+        if let Some(search) = self.output_search.as_mut() {
+            search.next();
+            self.scroll_to_match();
+        }
+    }
+
+    pub fn previous_match(&mut self) {
+        if let Some(search) = self.output_search.as_mut() {
+            search.previous();
+            self.scroll_to_match();
         }
     }
+
+    /// Searches any output added since the last update.
+    fn update_output_search(&mut self) {
+        if let Some(mut search) = self.output_search.take() {
+            if let Some(buffer) = self.active_buffer() {
+                search.update(&buffer.plain_lines);
+            }
+            self.output_search = Some(search);
+        }
+    }
+
+    /// Scrolls the current match into view, a third of the way down the
+    /// preview, and sideways to it if lines are not wrapped.
+    fn scroll_to_match(&mut self) {
+        let Some((search, buffer)) = self.output_search.as_ref().zip(self.active_buffer()) else {
+            return;
+        };
+        let Some(current) = search.current_match() else {
+            return;
+        };
+        let text = &buffer.plain_lines[current.line];
+        let start = outputsearch::match_ranges(text, &search.query)[current.index].0;
+        let column = Line::raw(&text[..start]).width();
+        self.horizontal_offset = column.saturating_sub(usize::from(self.viewport.width) / 3);
+        self.scroll_target = Some(current.line);
+    }
+
+    /// The line of output at the top of the view, as of the last render.
+    fn top_line(&self) -> usize {
+        match self.active_buffer() {
+            Some(buffer) => self.layout.line_at(self.scroll_offset).min(buffer.len()),
+            None => 0,
+        }
+    }
+
+    /// Scrolls just far enough for the selected test to be in view.
+    fn scroll_to_selected_test(&mut self) {
+        let Some(line) = self.test_report.selected_line() else {
+            return;
+        };
+        let height = usize::from(self.viewport.height).max(1);
+        if line < self.scroll_offset {
+            self.scroll_offset = line;
+        } else if line >= self.scroll_offset + height {
+            self.scroll_offset = line + 1 - height;
+        }
+    }
+
+    /// Builds the block title: the output tabs with the active one
+    /// highlighted, followed by the task status.
+    fn title(&self) -> Line<'static> {
+        let tab_label = |tab: PreviewTab, count: usize| {
+            let style = if self.active_tab == tab {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            let label = if count > 0 {
+                format!(" {} ({}) ", tab.name(), count)
+            } else {
+                format!(" {} ", tab.name())
+            };
+            Span::styled(label, style)
+        };
+
+        let mut spans = vec![
+            Span::raw("Preview "),
+            tab_label(PreviewTab::Docs, 0),
+            Span::raw("|"),
+            tab_label(PreviewTab::Source, 0),
+            Span::raw("|"),
+            tab_label(PreviewTab::Diff, 0),
+            Span::raw("|"),
+            tab_label(PreviewTab::Build, 0),
+            Span::raw("|"),
+            tab_label(PreviewTab::Stdout, 0),
+            Span::raw("|"),
+            tab_label(PreviewTab::Stderr, self.stderr_preview.len()),
+            Span::raw("|"),
+            tab_label(PreviewTab::Tests, self.test_report.failed()),
+        ];
+
+        if let Some(number) = self.reopened_run {
+            spans.push(Span::raw(format!(" [run #{}]", number)));
+        }
+        if let Some(input_file) = &self.input_file {
+            spans.push(Span::raw(format!(" [{}]", input_file)));
+        }
+        if let Some(mode) = self.run_mode {
+            spans.push(Span::raw(format!(" [{}]", mode.describe())));
+        }
+        if let Some(status) = self.status.describe() {
+            spans.push(Span::styled(
+                format!(" ({})", status),
+                Style::default().fg(self.status.color()),
+            ));
+        }
+        if let Some(verdict) = &self.verdict {
+            spans.push(verdict.badge());
+            if let Verdict::Wrong { expected } = verdict {
+                spans.push(Span::styled(
+                    format!(" expected {}", expected),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+        }
+        if matches!(self.status, TaskStatus::Building | TaskStatus::BuildFailed) {
+            spans.push(Span::raw(format!(" {}", self.build_progress.summary())));
+        }
+        if let Some(stats) = &self.stats {
+            spans.push(Span::raw(format!(" {}", stats.summary())));
+        }
+        if !self.show_colours {
+            spans.push(Span::raw(" [plain]"));
+        }
+        if !self.wrap {
+            spans.push(Span::raw(" [no wrap]"));
+        }
+        if let Some(search) = &self.output_search {
+            spans.push(Span::styled(
+                format!(" [{}]", search.describe()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    /// Returns the output shown in the active tab, or `None` for the tabs
+    /// whose lines are generated instead.
+    fn active_buffer(&self) -> Option<&OutputBuffer> {
+        match self.active_tab {
+            PreviewTab::Docs | PreviewTab::Source | PreviewTab::Diff => None,
+            PreviewTab::Build => Some(&self.build_preview),
+            PreviewTab::Stdout => Some(&self.file_preview),
+            PreviewTab::Stderr => Some(&self.stderr_preview),
+            PreviewTab::Tests => None,
+        }
+    }
+
+    fn generated_lines(&self, keys: &KeyBindings) -> Cow<'_, [Line<'static>]> {
+        match self.active_tab {
+            PreviewTab::Docs => Cow::Borrowed(&self.docs),
+            PreviewTab::Source => Cow::Borrowed(&self.source),
+            PreviewTab::Diff => Cow::Borrowed(&self.diff),
+            PreviewTab::Tests => Cow::Owned(self.test_report.lines(keys)),
+            _ => Cow::Borrowed(&[]),
+        }
+    }
+
+    /// Cycles through the tabs, starting the new tab from the top.
+    pub fn toggle_tab(&mut self) {
+        let tab = match self.active_tab {
+            PreviewTab::Docs => PreviewTab::Source,
+            PreviewTab::Source => PreviewTab::Diff,
+            PreviewTab::Diff => PreviewTab::Build,
+            PreviewTab::Build => PreviewTab::Stdout,
+            PreviewTab::Stdout => PreviewTab::Stderr,
+            PreviewTab::Stderr => PreviewTab::Tests,
+            PreviewTab::Tests => PreviewTab::Docs,
+        };
+        self.show_tab(tab);
+    }
+
+    fn show_tab(&mut self, tab: PreviewTab) {
+        self.active_tab = tab;
+        self.content_changed();
+        self.output_search = None;
+        self.scroll_to_top();
+    }
+
+    /// Shows the docs, source and diff of a day or task directory, such as
+    /// `day1` or `day1/task2`. They are only reloaded when the directory
+    /// changes, so this can be called every frame with the tree's selection.
+    pub fn show_selected(&mut self, path: Option<String>) {
+        if path == self.selected_path {
+            return;
+        }
+        self.docs = load_docs(path.as_deref());
+        self.diff = load_diff(path.as_deref());
+        self.source_file = path.as_deref().and_then(source_file);
+        self.selected_path = path;
+        self.reload_source();
+        if self.active_buffer().is_none() {
+            self.show_tab(self.active_tab);
+        }
+    }
+
+    /// Reads and highlights the selected task's source file again, e.g.
+    /// after it has been edited. The scroll position is kept.
+    pub fn reload_source(&mut self) {
+        self.source = match &self.source_file {
+            Some(source_file) => match std::fs::read_to_string(source_file) {
+                Ok(source) => highlight::highlight_with_line_numbers(&source),
+                Err(e) => vec![Line::from(format!("Failed to read {}: {}", source_file, e))],
+            },
+            None => vec![Line::from("Select a task to view its source")],
+        };
+        self.content_changed();
+    }
+
+    /// Switches between rendering ANSI colours and showing the output with
+    /// all escape sequences stripped.
+    pub fn toggle_colours(&mut self) {
+        self.show_colours = !self.show_colours;
+    }
+
+    /// Asks the running task to stop. The preview is marked as cancelled
+    /// once the child is gone.
+    pub fn cancel_task(&mut self) {
+        if let Some(task) = self.task.as_mut() {
+            task.cancel();
+        }
+        if let Some(tests) = self.tests.as_mut() {
+            tests.handle.cancel();
+        }
+    }
+
+    pub fn select_next_test(&mut self) {
+        self.test_report.select_next();
+        self.scroll_to_selected_test();
+    }
+
+    pub fn select_previous_test(&mut self) {
+        self.test_report.select_previous();
+        self.scroll_to_selected_test();
+    }
+
+    /// Shows or hides the failure of the selected test.
+    pub fn toggle_test(&mut self) {
+        self.test_report.toggle_selected();
+        self.content_changed();
+    }
+
+    /// Drains any output the running task has produced since the last call
+    /// and appends it to the preview. This never blocks, so it is safe to call
+    /// once per frame from the event loop.
+    ///
+    /// Returns a record of the run if the task ended during this call,
+    /// whether it finished, failed or was cancelled.
+    pub fn poll_output(&mut self) -> Option<RunRecord> {
+        let mut task = self.task.take()?;
+        let mut finished = None;
+        let mut solved_answer = None;
+
+        loop {
+            match task.output.try_recv() {
+                Ok(TaskOutput::Stdout(line)) => self.file_preview.push_line(&line),
+                Ok(TaskOutput::Stderr(line)) => self.stderr_preview.push_line(&line),
+                Ok(TaskOutput::Build(message)) => self.push_build_message(message),
+                Ok(TaskOutput::BuildStderr(line)) => self.build_preview.push_line(&line),
+                Ok(TaskOutput::Built(mode)) => {
+                    self.run_mode = Some(mode);
+                    self.status = TaskStatus::Running;
+                    self.show_tab(PreviewTab::Stdout);
+                }
+                Ok(TaskOutput::BuildFailed(stats)) => {
+                    finished = Some((TaskStatus::BuildFailed, stats));
+                    break;
+                }
+                Ok(TaskOutput::Finished(status, stats)) => {
+                    finished = Some((TaskStatus::Finished(status), stats));
+                    break;
+                }
+                Ok(TaskOutput::Solved(answer, stats)) => {
+                    self.file_preview
+                        .push_line(&format!("\x1b[1mAnswer: {}\x1b[0m", answer));
+                    solved_answer = Some(answer);
+                    finished = Some((TaskStatus::Solved, stats));
+                    break;
+                }
+                Ok(TaskOutput::Failed(stats)) => {
+                    finished = Some((TaskStatus::Failed, stats));
+                    break;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    finished = Some((TaskStatus::Finished(None), RunStats::default()));
+                    break;
+                }
+                Ok(TaskOutput::Cancelled(stats)) => {
+                    finished = Some((TaskStatus::Cancelled, stats));
+                    break;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.task = Some(task);
+                    break;
+                }
+            }
+        }
+
+        let (status, stats) = finished?;
+        self.status = status;
+        self.stats = Some(stats);
+        let task_path = self.task_path.clone()?;
+
+        let answer = match status {
+            TaskStatus::Solved => solved_answer,
+            TaskStatus::Finished(Some(status)) if status.success() => {
+                answers::extract_answer(&self.file_preview.plain_lines)
+            }
+            _ => None,
+        };
+        // A cancelled run gave no answer, but that does not make it wrong.
+        self.verdict = if status == TaskStatus::Cancelled {
+            None
+        } else {
+            let input_name = answers::input_name(self.input_file.as_deref());
+            Answers::load(&task_path).verify(&input_name, answer.as_deref())
+        };
+        Some(RunRecord {
+            task_path,
+            input_file: self.input_file.clone(),
+            status,
+            stats,
+            answer,
+            verdict: self.verdict.clone(),
+            output: self.file_preview.raw.clone(),
+            finished_at: SystemTime::now(),
+        })
+    }
+
+    /// Shows the output of an earlier run again, as if it had just finished.
+    /// Any task still running is stopped, as when starting a new one.
+    pub fn show_run(&mut self, record: &RunRecord, number: usize) {
+        self.clear_output();
+        for line in record.output.lines() {
+            self.file_preview.push_line(line);
+        }
+        self.task = None;
+        self.tests = None;
+        self.task_path = Some(record.task_path.clone());
+        self.input_file = record.input_file.clone();
+        self.status = record.status;
+        self.stats = Some(record.stats);
+        self.verdict = record.verdict.clone();
+        self.reopened_run = Some(number);
+        self.show_tab(PreviewTab::Stdout);
+    }
+
+    fn push_build_message(&mut self, message: BuildMessage) {
+        match message {
+            BuildMessage::Artifact { fresh: true, .. } => self.build_progress.fresh += 1,
+            BuildMessage::Artifact { name, .. } => {
+                self.build_progress.compiled += 1;
+                self.build_preview
+                    .push_line(&format!("\x1b[1;32m   Compiled\x1b[0m {}", name));
+            }
+            BuildMessage::Diagnostic(diagnostic) => {
+                match &diagnostic.rendered {
+                    Some(rendered) => rendered
+                        .lines()
+                        .for_each(|line| self.build_preview.push_line(line)),
+                    None => self.build_preview.push_line(&diagnostic.summary()),
+                }
+                // rustc ends with summary messages such as "aborting due to
+                // 1 previous error", which are not counted themselves.
+                if diagnostic.is_error() && !diagnostic.message.starts_with("aborting due to") {
+                    self.build_progress.errors.push(diagnostic);
+                } else if diagnostic.level == "warning" && diagnostic.location.is_some() {
+                    self.build_progress.warnings += 1;
+                }
+            }
+            BuildMessage::Finished { success } => {
+                let result = if success { "succeeded" } else { "failed" };
+                self.build_preview.push_line(&format!(
+                    "Build {}: {}",
+                    result,
+                    self.build_progress.summary()
+                ));
+            }
+        }
+    }
+
+    /// Summarises a failed build or run, or returns `None` if the task
+    /// succeeded.
+    pub fn failure_message(&self, status: TaskStatus) -> Option<String> {
+        let heading = match status {
+            TaskStatus::BuildFailed => return Some(self.build_failure_message()),
+            TaskStatus::Finished(Some(status)) if status.success() => return None,
+            TaskStatus::Finished(_) => format!("Task failed with {}", status.describe()?),
+            TaskStatus::Failed => "Task failed".to_string(),
+            _ => return None,
+        };
+        // Start at the panic message if there is one, since a backtrace
+        // would otherwise push it out of view.
+        let lines = &self.stderr_preview.plain_lines;
+        let start = lines
+            .iter()
+            .rposition(|line| line.contains("panicked at"))
+            .unwrap_or(lines.len().saturating_sub(ERROR_CONTEXT_LINES));
+        let end = lines.len().min(start + ERROR_CONTEXT_LINES);
+        let context = lines[start..end].join("\n");
+        Some(format!("{}\n\n{}", heading, context))
+    }
+
+    /// Lists the compiler errors with their locations, falling back to the
+    /// tail of cargo's output when the failure was not a compile error.
+    fn build_failure_message(&self) -> String {
+        let errors = &self.build_progress.errors;
+        let context = if errors.is_empty() {
+            let lines = &self.build_preview.plain_lines;
+            lines[lines.len().saturating_sub(ERROR_CONTEXT_LINES)..].join("\n")
+        } else {
+            errors
+                .iter()
+                .take(ERROR_CONTEXT_LINES)
+                .map(Diagnostic::summary)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        format!("Build failed with {} errors\n\n{}", errors.len(), context)
+    }
+
+    /// Starts running the task in the background, through the `aoc` runner if
+    /// it is in the solution registry and otherwise from its own executable.
+    /// The title says which was used. Output is picked up by
+    /// `poll_output`. Starting a new task while another is running drops the
+    /// old handle, which terminates the previous child.
+    pub fn run_task(
+        &mut self,
+        task_path: &str,
+        input_file: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.clear_output();
+        self.show_tab(PreviewTab::Build);
+
+        let task = taskrunner::spawn_task(task_path, input_file)?;
+        self.task = Some(task);
+        self.tests = None;
+        self.task_path = Some(task_path.to_string());
+        self.input_file = input_file.map(str::to_string);
+        self.status = TaskStatus::Building;
+        self.stats = None;
+        self.verdict = None;
+        Ok(())
+    }
+
+    /// Runs `cargo test` for each of the tasks in turn and shows the results
+    /// in the tests tab. Output is picked up by `poll_tests`. Like
+    /// `run_task`, this stops whatever was running before.
+    pub fn run_tests(&mut self, task_paths: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut pending = VecDeque::from(task_paths);
+        let task_path = pending.pop_front().ok_or("no tasks to test")?;
+        let handle = taskrunner::spawn_tests(&task_path)?;
+
+        self.clear_output();
+        self.test_report = TestReport::default();
+        self.test_report.start_task(&task_path);
+        self.task = None;
+        self.tests = Some(TestRun {
+            handle,
+            task_path,
+            pending,
+            results: Vec::new(),
+            status: None,
+            all_passed: true,
+            run_time: Duration::ZERO,
+        });
+        self.task_path = None;
+        self.input_file = None;
+        self.status = TaskStatus::Running;
+        self.stats = None;
+        self.verdict = None;
+        self.show_tab(PreviewTab::Tests);
+        Ok(())
+    }
+
+    /// Drains the output of a test run like `poll_output` does for a task,
+    /// moving on to the next task's tests when one finishes.
+    ///
+    /// Returns whether each task's tests passed once they have all finished
+    /// or the run dies partway, and nothing if it is cancelled.
+    pub fn poll_tests(&mut self) -> Option<Vec<(String, bool)>> {
+        let mut tests = self.tests.take()?;
+
+        let mut received = false;
+        let mut cancelled = false;
+        let finished = loop {
+            match tests.handle.output.try_recv() {
+                Ok(TaskOutput::Stdout(line)) => {
+                    self.file_preview.push_line(&line);
+                    if let Some(line) = self.file_preview.plain_lines.last() {
+                        self.test_report.push_line(line);
+                    }
+                    received = true;
+                }
+                Ok(TaskOutput::Stderr(line)) => self.stderr_preview.push_line(&line),
+                Ok(TaskOutput::Finished(status, stats)) => {
+                    let passed = status.is_some_and(|status| status.success())
+                        && !self.test_report.has_failures(&tests.task_path);
+                    tests.results.push((tests.task_path.clone(), passed));
+                    tests.run_time += stats.run_time.unwrap_or_default();
+                    if tests.all_passed {
+                        tests.status = status;
+                    }
+                    tests.all_passed &= passed;
+                    if !self.start_next_tests(&mut tests) {
+                        break true;
+                    }
+                }
+                Ok(TaskOutput::Cancelled(_)) => {
+                    cancelled = true;
+                    break false;
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Disconnected) => {
+                    // The tests stopped without finishing, so the task being
+                    // tested failed and the rest are never tested.
+                    tests.results.push((tests.task_path.clone(), false));
+                    if tests.all_passed {
+                        tests.status = None;
+                    }
+                    tests.all_passed = false;
+                    break true;
+                }
+                Err(TryRecvError::Empty) => break false,
+            }
+        };
+
+        // Results go in under their task, not at the end, so the report is
+        // laid out again once for everything that arrived.
+        if received {
+            self.content_changed();
+        }
+        if cancelled {
+            self.status = TaskStatus::Cancelled;
+            return None;
+        }
+        if !finished {
+            self.tests = Some(tests);
+            return None;
+        }
+
+        self.status = TaskStatus::Finished(tests.status);
+        self.stats = Some(RunStats {
+            run_time: Some(tests.run_time),
+            ..RunStats::default()
+        });
+        Some(tests.results)
+    }
+
+    /// Starts testing the next pending task, returning false once there are
+    /// none left.
+    fn start_next_tests(&mut self, tests: &mut TestRun) -> bool {
+        while let Some(task_path) = tests.pending.pop_front() {
+            self.test_report.start_task(&task_path);
+            self.content_changed();
+            match taskrunner::spawn_tests(&task_path) {
+                Ok(handle) => {
+                    tests.handle = handle;
+                    tests.task_path = task_path;
+                    return true;
+                }
+                Err(e) => {
+                    self.stderr_preview
+                        .push_line(&format!("Failed to test {}: {}", task_path, e));
+                    tests.results.push((task_path, false));
+                    tests.all_passed = false;
+                }
+            }
+        }
+        false
+    }
+
+    fn clear_output(&mut self) {
+        self.reopened_run = None;
+        self.run_mode = None;
+        self.file_preview.clear();
+        self.stderr_preview.clear();
+        self.build_preview.clear();
+        self.build_progress = BuildProgress::default();
+        self.content_changed();
+    }
+}
+
+/// Returns the file a task's solution is in: its `lib.rs`, or `main.rs` for
+/// tasks that are not split into a library.
+fn source_file(path: &str) -> Option<String> {
+    if !path.contains('/') {
+        return None;
+    }
+    ["lib.rs", "main.rs"]
+        .iter()
+        .map(|file| format!("{}/src/{}", path, file))
+        .find(|source_file| Path::new(source_file).is_file())
+}
+
+/// Renders a day's README, or the module documentation at the top of a
+/// task's source file.
+fn load_docs(path: Option<&str>) -> Vec<Line<'static>> {
+    let Some(path) = path else {
+        return vec![Line::from("Select a day or task to read about it")];
+    };
+
+    if !path.contains('/') {
+        return match std::fs::read_to_string(format!("{}/README.md", path)) {
+            Ok(readme) => markdown::render(&readme),
+            Err(_) => vec![Line::from(format!("No README.md in {}", path))],
+        };
+    }
+
+    let docs = source_file(path)
+        .and_then(|source_file| std::fs::read_to_string(source_file).ok())
+        .and_then(|source| markdown::module_docs(&source));
+    match docs {
+        Some(docs) => markdown::render(&docs),
+        None => vec![Line::from(format!(
+            "No description for {}. Add one as //! comments at the top of src/lib.rs.",
+            path
+        ))],
+    }
+}
+
+/// Diffs the source of task1 against task2 for the day of a day or task
+/// directory, to show what part two needed.
+fn load_diff(path: Option<&str>) -> Vec<Line<'static>> {
+    let Some(day) = path.and_then(|path| path.split('/').next()) else {
+        return vec![Line::from("Select a day to compare its tasks")];
+    };
+    let files = ["task1", "task2"].map(|task| source_file(&format!("{}/{}", day, task)));
+    let [Some(old_file), Some(new_file)] = files else {
+        return vec![Line::from(format!(
+            "{} does not have two tasks to compare",
+            day
+        ))];
+    };
+
+    let read = |file: &str| std::fs::read_to_string(file).map_err(|e| format!("{}: {}", file, e));
+    let (old, new) = match (read(&old_file), read(&new_file)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) | (_, Err(e)) => return vec![Line::from(format!("Failed to read {}", e))],
+    };
+    let mut lines = vec![
+        Line::styled(format!("--- {}", old_file), Color::Red),
+        Line::styled(format!("+++ {}", new_file), Color::Green),
+    ];
+    lines.extend(diff::unified(&diff::diff_lines(&old, &new)));
+    lines
 }
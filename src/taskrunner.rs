@@ -0,0 +1,982 @@
+use crate::buildmessages::{self, BuildMessage};
+use crate::taskfinder;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncBufReadExt, AsyncRead};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+
+/// How long a cancelled task is given to exit after SIGTERM before it is
+/// killed outright.
+const TERMINATE_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Messages sent from the background task runner to whoever is displaying
+/// the task.
+pub enum TaskOutput {
+    Stdout(String),
+    Stderr(String),
+    /// A message from cargo while the task is being built.
+    Build(BuildMessage),
+    /// A line cargo printed to stderr while building, such as a failure to
+    /// fetch dependencies.
+    BuildStderr(String),
+    /// The build succeeded, or was not needed, and the task itself has
+    /// started in the given mode.
+    Built(RunMode),
+    BuildFailed(RunStats),
+    /// The task ran to the end. The status is `None` if it could not be
+    /// started or its status could not be collected.
+    Finished(Option<ExitStatus>, RunStats),
+    /// A registered solution returned its answer.
+    Solved(String, RunStats),
+    /// A registered solution panicked, its input could not be read or the
+    /// runner exited without an answer. The details are sent on stderr
+    /// first.
+    Failed(RunStats),
+    /// The task was killed. The stats cover what was measured before then,
+    /// such as the build.
+    Cancelled(RunStats),
+}
+
+/// Measurements for a single execution of a task. Durations are saved as
+/// seconds.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunStats {
+    #[serde(serialize_with = "seconds", deserialize_with = "from_seconds")]
+    pub build_time: Option<Duration>,
+    /// Time spent parsing the input, for registered solutions.
+    #[serde(serialize_with = "seconds", deserialize_with = "from_seconds")]
+    pub parse_time: Option<Duration>,
+    /// Wall-clock time of the task itself, excluding the build.
+    #[serde(serialize_with = "seconds", deserialize_with = "from_seconds")]
+    pub run_time: Option<Duration>,
+    /// Peak resident set size of the task in kilobytes, where the platform
+    /// reports it.
+    pub peak_rss_kb: Option<u64>,
+}
+
+impl RunStats {
+    /// Formats the stats for display, e.g. "build 1.20s, run 340ms, peak 12.3 MB".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(build_time) = self.build_time {
+            parts.push(format!("build {}", format_duration(build_time)));
+        }
+        if let Some(parse_time) = self.parse_time {
+            parts.push(format!("parse {}", format_duration(parse_time)));
+        }
+        if let Some(run_time) = self.run_time {
+            parts.push(format!("run {}", format_duration(run_time)));
+        }
+        if let Some(peak_rss_kb) = self.peak_rss_kb {
+            parts.push(format!("peak {}", format_kilobytes(peak_rss_kb)));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Saves a duration as a number of seconds.
+fn seconds<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| duration.as_secs_f64())
+        .serialize(serializer)
+}
+
+/// Reads a duration saved by `seconds`.
+fn from_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    Option::<f64>::deserialize(deserializer)?
+        .map(|seconds| Duration::try_from_secs_f64(seconds).map_err(D::Error::custom))
+        .transpose()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() >= 1 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else if duration.as_millis() >= 1 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{}µs", duration.as_micros())
+    }
+}
+
+pub fn format_kilobytes(kilobytes: u64) -> String {
+    if kilobytes >= 1024 * 1024 {
+        format!("{:.1} GB", kilobytes as f64 / (1024.0 * 1024.0))
+    } else if kilobytes >= 1024 {
+        format!("{:.1} MB", kilobytes as f64 / 1024.0)
+    } else {
+        format!("{} KB", kilobytes)
+    }
+}
+
+/// A task running in the background. Dropping the handle terminates the
+/// child.
+pub struct TaskHandle {
+    pub output: UnboundedReceiver<TaskOutput>,
+    cancel_sender: Option<oneshot::Sender<()>>,
+}
+
+impl TaskHandle {
+    /// Asks the task to stop. The child is sent SIGTERM and, if it has not
+    /// exited within `TERMINATE_GRACE_PERIOD`, SIGKILL. A `Cancelled`
+    /// message is sent once the child is gone.
+    pub fn cancel(&mut self) {
+        if let Some(cancel_sender) = self.cancel_sender.take() {
+            let _ = cancel_sender.send(());
+        }
+    }
+}
+
+/// The result of running one child process to completion.
+enum ProcessOutcome {
+    Exited {
+        status: Option<ExitStatus>,
+        elapsed: Duration,
+        peak_rss_kb: Option<u64>,
+    },
+    FailedToStart(std::io::Error),
+    Cancelled,
+}
+
+/// How a task is run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunMode {
+    /// The day's registered solution, run by the `aoc` runner so that parsing
+    /// and solving are timed separately.
+    Solution,
+    /// The task's own executable, for tasks without a registered solution.
+    Binary,
+}
+
+impl RunMode {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            RunMode::Solution => "aoc runner",
+            RunMode::Binary => "task binary",
+        }
+    }
+}
+
+/// What the `aoc` runner reports once a solution has returned its answer.
+struct SolvedMessage {
+    answer: String,
+    parse_time: Duration,
+    solve_time: Duration,
+}
+
+/// The `aoc` runner as last built this session, along with the solutions
+/// registered in it.
+#[derive(Clone)]
+struct SolutionRunner {
+    executable: PathBuf,
+    solutions: BTreeSet<(u8, u8)>,
+    /// When the build started. Sources modified after this are not in the
+    /// executable.
+    built_at: SystemTime,
+}
+
+impl SolutionRunner {
+    /// Whether the executable is still there and none of the sources it was
+    /// built from have changed since.
+    fn is_fresh(&self) -> bool {
+        let sources = ["Cargo.toml", "Cargo.lock", "aoc", "aoc-common"]
+            .into_iter()
+            .map(PathBuf::from)
+            .chain(
+                self.solutions
+                    .iter()
+                    .map(|(day, part)| PathBuf::from(taskfinder::task_path(*day, *part))),
+            );
+        self.executable.is_file()
+            && !sources
+                .into_iter()
+                .any(|source| modified_since(&source, self.built_at))
+    }
+}
+
+/// The runner is built at most once at a time: a run that needs it while it
+/// is being built waits for that build rather than starting another.
+static SOLUTION_RUNNER: tokio::sync::Mutex<Option<SolutionRunner>> =
+    tokio::sync::Mutex::const_new(None);
+
+/// The solutions registered in the runner when it was last built, readable
+/// without waiting for a build in progress.
+static REGISTERED_SOLUTIONS: Mutex<Option<BTreeSet<(u8, u8)>>> = Mutex::new(None);
+
+/// Lists the registered solutions as `(day, part)`, or `None` if the runner
+/// has not been built yet this session.
+pub fn registered_solutions() -> Option<BTreeSet<(u8, u8)>> {
+    REGISTERED_SOLUTIONS
+        .lock()
+        .ok()
+        .and_then(|solutions| solutions.clone())
+}
+
+/// Builds the runner if it is not up to date and returns the registered
+/// solutions, or `None` if it failed to build. The build output is
+/// discarded.
+pub async fn load_solutions() -> Option<BTreeSet<(u8, u8)>> {
+    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (_cancel_sender, mut cancel_receiver) = oneshot::channel();
+    let mut stats = RunStats::default();
+    solution_runner(&sender, &mut stats, &mut cancel_receiver)
+        .await
+        .ok()
+        .map(|runner| runner.solutions)
+}
+
+/// Why a task could not be built. The details have already been sent as
+/// build output.
+enum BuildError {
+    Failed,
+    Cancelled,
+}
+
+/// What a cargo build produced.
+struct Built {
+    success: bool,
+    /// The executables built, by target name.
+    executables: HashMap<String, PathBuf>,
+}
+
+/// What a task is run with once it has been built.
+#[derive(Clone)]
+pub struct Executable {
+    pub mode: RunMode,
+    pub path: PathBuf,
+}
+
+/// Runs the task, streaming progress and output over the returned handle.
+/// Tasks in the solution registry are run by the `aoc` runner, which is
+/// built once per session and rebuilt only when its sources change, so
+/// edits are picked up without paying for cargo on every run. Anything else
+/// is built with cargo and run from its own executable, as are all tasks
+/// while the runner fails to build.
+///
+/// `input_file` is relative to the task directory. Tasks fall back to
+/// `input/input.txt` without it.
+pub fn spawn_task(
+    task_path: &str,
+    input_file: Option<&str>,
+) -> Result<TaskHandle, Box<dyn std::error::Error>> {
+    let full_path = task_directory(task_path)?;
+    let task = taskfinder::parse_task_path(task_path);
+    let input_file = input_file.map(str::to_string);
+    Ok(spawn_handle(
+        move |sender, mut cancel_receiver| async move {
+            let mut stats = RunStats::default();
+            let executable =
+                build_task(task, &full_path, &sender, &mut stats, &mut cancel_receiver);
+            match executable.await {
+                Ok(executable) => {
+                    run_executable(
+                        executable,
+                        task,
+                        full_path,
+                        input_file,
+                        stats,
+                        &sender,
+                        &mut cancel_receiver,
+                    )
+                    .await
+                }
+                Err(BuildError::Failed) => {
+                    let _ = sender.send(TaskOutput::BuildFailed(stats));
+                }
+                Err(BuildError::Cancelled) => {
+                    let _ = sender.send(TaskOutput::Cancelled(stats));
+                }
+            }
+        },
+    ))
+}
+
+/// Runs a task that has already been built, for example by `build_tasks`.
+pub fn spawn_built(
+    task_path: &str,
+    input_file: Option<&str>,
+    executable: Executable,
+) -> Result<TaskHandle, Box<dyn std::error::Error>> {
+    let full_path = task_directory(task_path)?;
+    let task = taskfinder::parse_task_path(task_path);
+    let input_file = input_file.map(str::to_string);
+    Ok(spawn_handle(
+        move |sender, mut cancel_receiver| async move {
+            run_executable(
+                executable,
+                task,
+                full_path,
+                input_file,
+                RunStats::default(),
+                &sender,
+                &mut cancel_receiver,
+            )
+            .await
+        },
+    ))
+}
+
+/// Builds everything the tasks need in one go, so that they can then be run
+/// in parallel without waiting on each other's builds: the `aoc` runner for
+/// registered solutions and a single cargo build for the rest. Returns the
+/// executable of each task that built.
+pub async fn build_tasks(tasks: &[(u8, u8)]) -> HashMap<(u8, u8), Executable> {
+    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (_cancel_sender, mut cancel_receiver) = oneshot::channel();
+    let mut stats = RunStats::default();
+    let mut executables = HashMap::new();
+    if tasks.is_empty() {
+        return executables;
+    }
+
+    let runner = solution_runner(&sender, &mut stats, &mut cancel_receiver).await;
+    let mut unregistered = Vec::new();
+    for &task in tasks {
+        match &runner {
+            Ok(runner) if runner.solutions.contains(&task) => {
+                let executable = Executable {
+                    mode: RunMode::Solution,
+                    path: runner.executable.clone(),
+                };
+                executables.insert(task, executable);
+            }
+            _ => unregistered.push(task),
+        }
+    }
+    if unregistered.is_empty() {
+        return executables;
+    }
+
+    let Ok(workspace) = std::env::current_dir() else {
+        return executables;
+    };
+    let mut command = Command::new("cargo");
+    // Keep going so that one task failing to build does not fail the rest.
+    command
+        .args(["build", "--keep-going"])
+        .current_dir(workspace);
+    for &(day, part) in &unregistered {
+        command.args(["--package", &package_name(day, part)]);
+    }
+    if let Some(mut built) = build(command, &sender, &mut stats, &mut cancel_receiver).await {
+        for (day, part) in unregistered {
+            if let Some(path) = built.executables.remove(&package_name(day, part)) {
+                let executable = Executable {
+                    mode: RunMode::Binary,
+                    path,
+                };
+                executables.insert((day, part), executable);
+            }
+        }
+    }
+    executables
+}
+
+/// The task's directory, checking that it exists.
+fn task_directory(task_path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let full_path = std::env::current_dir()?.join(task_path);
+    if !full_path.is_dir() {
+        return Err(format!("{} is not a directory", full_path.display()).into());
+    }
+    Ok(full_path)
+}
+
+/// The cargo package of a task, which is also the name of its executable.
+fn package_name(day: u8, part: u8) -> String {
+    format!("day{}-task{}", day, part)
+}
+
+/// Starts `run` in the background with the sending half of a new task's
+/// output and its cancel receiver, and returns the task's handle.
+fn spawn_handle<F>(
+    run: impl FnOnce(UnboundedSender<TaskOutput>, oneshot::Receiver<()>) -> F,
+) -> TaskHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let (cancel_sender, cancel_receiver) = oneshot::channel();
+    // The cancel receiver also resolves when its sender is dropped, so
+    // dropping the handle stops the task too.
+    tokio::spawn(run(sender, cancel_receiver));
+    TaskHandle {
+        output: receiver,
+        cancel_sender: Some(cancel_sender),
+    }
+}
+
+/// Finds or builds what the task is run with: the `aoc` runner if the task
+/// is registered in it, or otherwise the task's own executable.
+async fn build_task(
+    task: Option<(u8, u8)>,
+    full_path: &Path,
+    sender: &UnboundedSender<TaskOutput>,
+    stats: &mut RunStats,
+    cancel_receiver: &mut oneshot::Receiver<()>,
+) -> Result<Executable, BuildError> {
+    match solution_runner(sender, stats, cancel_receiver).await {
+        Ok(runner) if task.is_some_and(|task| runner.solutions.contains(&task)) => {
+            return Ok(Executable {
+                mode: RunMode::Solution,
+                path: runner.executable,
+            });
+        }
+        Ok(_) => {}
+        Err(BuildError::Failed) => {
+            let _ = sender.send(TaskOutput::BuildStderr(
+                "The aoc runner failed to build, so the task is built on its own".to_string(),
+            ));
+        }
+        Err(BuildError::Cancelled) => return Err(BuildError::Cancelled),
+    }
+
+    let mut command = Command::new("cargo");
+    command.arg("build").current_dir(full_path);
+    let built = build(command, sender, stats, cancel_receiver)
+        .await
+        .ok_or(BuildError::Cancelled)?;
+    if !built.success {
+        return Err(BuildError::Failed);
+    }
+    match built.executables.into_values().next() {
+        Some(path) => Ok(Executable {
+            mode: RunMode::Binary,
+            path,
+        }),
+        None => {
+            let _ = sender.send(TaskOutput::BuildStderr(
+                "cargo did not report an executable for this task".to_string(),
+            ));
+            Err(BuildError::Failed)
+        }
+    }
+}
+
+/// Returns the `aoc` runner, building it first if this is the first time it
+/// is needed this session or its sources have changed since it was built.
+async fn solution_runner(
+    sender: &UnboundedSender<TaskOutput>,
+    stats: &mut RunStats,
+    cancel_receiver: &mut oneshot::Receiver<()>,
+) -> Result<SolutionRunner, BuildError> {
+    let mut runner = match SOLUTION_RUNNER.try_lock() {
+        Ok(runner) => runner,
+        Err(_) => {
+            let _ = sender.send(TaskOutput::BuildStderr(
+                "Waiting for the aoc runner to finish building".to_string(),
+            ));
+            tokio::select! {
+                runner = SOLUTION_RUNNER.lock() => runner,
+                _ = &mut *cancel_receiver => return Err(BuildError::Cancelled),
+            }
+        }
+    };
+    if let Some(runner) = runner.as_ref().filter(|runner| runner.is_fresh()) {
+        return Ok(runner.clone());
+    }
+
+    let built_at = SystemTime::now();
+    let workspace = std::env::current_dir().map_err(|e| {
+        let _ = sender.send(TaskOutput::BuildStderr(format!(
+            "Failed to find the workspace: {}",
+            e
+        )));
+        BuildError::Failed
+    })?;
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--package", "aoc"])
+        .current_dir(&workspace);
+    let built = build(command, sender, stats, cancel_receiver)
+        .await
+        .ok_or(BuildError::Cancelled)?;
+    let executable = match built.executables.get("aoc") {
+        Some(executable) if built.success => executable.clone(),
+        _ => return Err(BuildError::Failed),
+    };
+
+    let listed = Command::new(&executable)
+        .arg("list")
+        .current_dir(&workspace)
+        .output()
+        .await;
+    let solutions = match listed {
+        Ok(listed) if listed.status.success() => {
+            parse_solutions(&String::from_utf8_lossy(&listed.stdout))
+        }
+        _ => {
+            let _ = sender.send(TaskOutput::BuildStderr(
+                "The aoc runner failed to list its solutions".to_string(),
+            ));
+            return Err(BuildError::Failed);
+        }
+    };
+    if let Ok(mut registered) = REGISTERED_SOLUTIONS.lock() {
+        *registered = Some(solutions.clone());
+    }
+    Ok(runner
+        .insert(SolutionRunner {
+            executable,
+            solutions,
+            built_at,
+        })
+        .clone())
+}
+
+/// Reads the `day part` lines that `aoc list` prints.
+fn parse_solutions(listing: &str) -> BTreeSet<(u8, u8)> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let (day, part) = line.split_once(' ')?;
+            Some((day.parse().ok()?, part.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Whether any Rust source, manifest or lock file at or under `path` was
+/// modified after `time`. Build output and puzzle inputs are skipped.
+fn modified_since(path: &Path, time: SystemTime) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if metadata.is_dir() {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return false;
+        };
+        return entries
+            .flatten()
+            .filter(|entry| !matches!(entry.file_name().to_str(), Some("target" | "input")))
+            .any(|entry| modified_since(&entry.path(), time));
+    }
+    let is_source = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "rs" | "toml" | "lock"));
+    is_source && metadata.modified().is_ok_and(|modified| modified > time)
+}
+
+/// Reads the message the `aoc` runner prints when a solution has returned,
+/// or `None` if the line is ordinary output.
+fn parse_solved_message(line: &str) -> Option<(SolvedMessage, Option<String>)> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    if value["reason"] != "solved" {
+        return None;
+    }
+    let duration = |key: &str| value[key].as_f64().map(Duration::from_secs_f64);
+    let message = SolvedMessage {
+        answer: value["answer"].as_str()?.to_string(),
+        parse_time: duration("parse_time")?,
+        solve_time: duration("solve_time")?,
+    };
+    Some((message, value["visualisation"].as_str().map(str::to_string)))
+}
+
+/// Runs `command`, a cargo build, with JSON messages and forwards its
+/// progress to `sender`, adding the time it took to `stats`. Returns `None`
+/// if it was cancelled.
+async fn build(
+    mut command: Command,
+    sender: &UnboundedSender<TaskOutput>,
+    stats: &mut RunStats,
+    cancel_receiver: &mut oneshot::Receiver<()>,
+) -> Option<Built> {
+    command
+        .arg("--quiet")
+        .arg("--message-format=json-diagnostic-rendered-ansi");
+    let executables: Arc<Mutex<HashMap<String, PathBuf>>> = Arc::default();
+    let on_stdout = {
+        let sender = sender.clone();
+        let executables = executables.clone();
+        move |line: String| {
+            let message = match buildmessages::parse_line(&line) {
+                Some(message) => message,
+                None => return,
+            };
+            if let BuildMessage::Artifact {
+                name,
+                executable: Some(path),
+                ..
+            } = &message
+            {
+                if let Ok(mut executables) = executables.lock() {
+                    executables.insert(name.clone(), path.clone());
+                }
+            }
+            let _ = sender.send(TaskOutput::Build(message));
+        }
+    };
+    let on_stderr = {
+        let sender = sender.clone();
+        move |line| {
+            let _ = sender.send(TaskOutput::BuildStderr(line));
+        }
+    };
+
+    let success = match run_process(command, on_stdout, on_stderr, cancel_receiver).await {
+        ProcessOutcome::Exited {
+            status, elapsed, ..
+        } => {
+            *stats.build_time.get_or_insert_default() += elapsed;
+            status.is_some_and(|status| status.success())
+        }
+        ProcessOutcome::FailedToStart(e) => {
+            let _ = sender.send(TaskOutput::BuildStderr(format!(
+                "Failed to start cargo: {}",
+                e
+            )));
+            false
+        }
+        ProcessOutcome::Cancelled => return None,
+    };
+    let executables = executables
+        .lock()
+        .map(|mut executables| std::mem::take(&mut *executables))
+        .unwrap_or_default();
+    Some(Built {
+        success,
+        executables,
+    })
+}
+
+/// Runs a built task in its directory and reports how it ended. The
+/// executable is run directly rather than through `cargo run`, which keeps
+/// cargo's own time and memory out of the run stats, and it is a child
+/// process either way, so cancelling or quitting always stops it.
+async fn run_executable(
+    executable: Executable,
+    task: Option<(u8, u8)>,
+    full_path: PathBuf,
+    input_file: Option<String>,
+    mut stats: RunStats,
+    sender: &UnboundedSender<TaskOutput>,
+    cancel_receiver: &mut oneshot::Receiver<()>,
+) {
+    let mode = executable.mode;
+    let mut run = Command::new(executable.path);
+    match (mode, task) {
+        (RunMode::Solution, Some((day, part))) => {
+            let input_path = full_path.join(
+                input_file
+                    .as_deref()
+                    .unwrap_or(aoc_common::input::DEFAULT_INPUT),
+            );
+            run.args(["run", "--message-format=json"])
+                .arg(day.to_string())
+                .arg(part.to_string())
+                .arg(input_path);
+        }
+        _ => {
+            run.args(input_file);
+        }
+    }
+    run.current_dir(&full_path)
+        // `colored` turns itself off when stdout is not a terminal.
+        .env("CLICOLOR_FORCE", "1");
+    let _ = sender.send(TaskOutput::Built(mode));
+
+    let solved: Arc<Mutex<Option<SolvedMessage>>> = Arc::default();
+    let on_stdout = {
+        let sender = sender.clone();
+        let solved = solved.clone();
+        move |line: String| {
+            let message = match mode {
+                RunMode::Solution => parse_solved_message(&line),
+                RunMode::Binary => None,
+            };
+            let Some((message, visualisation)) = message else {
+                let _ = sender.send(TaskOutput::Stdout(line));
+                return;
+            };
+            for line in visualisation.iter().flat_map(|v| v.lines()) {
+                let _ = sender.send(TaskOutput::Stdout(line.to_string()));
+            }
+            if let Ok(mut solved) = solved.lock() {
+                *solved = Some(message);
+            }
+        }
+    };
+    let on_stderr = {
+        let sender = sender.clone();
+        move |line| {
+            let _ = sender.send(TaskOutput::Stderr(line));
+        }
+    };
+    let outcome = run_process(run, on_stdout, on_stderr, cancel_receiver).await;
+    let solved = solved.lock().ok().and_then(|mut solved| solved.take());
+    match (outcome, mode) {
+        (
+            ProcessOutcome::Exited {
+                status,
+                elapsed,
+                peak_rss_kb,
+            },
+            RunMode::Binary,
+        ) => {
+            stats.run_time = Some(elapsed);
+            stats.peak_rss_kb = peak_rss_kb;
+            let _ = sender.send(TaskOutput::Finished(status, stats));
+        }
+        (
+            ProcessOutcome::Exited {
+                status,
+                elapsed,
+                peak_rss_kb,
+            },
+            RunMode::Solution,
+        ) => {
+            stats.peak_rss_kb = peak_rss_kb;
+            match solved {
+                Some(solved) if status.is_some_and(|status| status.success()) => {
+                    stats.parse_time = Some(solved.parse_time);
+                    stats.run_time = Some(solved.solve_time);
+                    let _ = sender.send(TaskOutput::Solved(solved.answer, stats));
+                }
+                _ => {
+                    stats.run_time = Some(elapsed);
+                    let _ = sender.send(TaskOutput::Failed(stats));
+                }
+            }
+        }
+        (ProcessOutcome::FailedToStart(e), mode) => {
+            let _ = sender.send(TaskOutput::Stderr(format!("Failed to start task: {}", e)));
+            let _ = sender.send(match mode {
+                RunMode::Solution => TaskOutput::Failed(stats),
+                RunMode::Binary => TaskOutput::Finished(None, stats),
+            });
+        }
+        (ProcessOutcome::Cancelled, _) => {
+            let _ = sender.send(TaskOutput::Cancelled(stats));
+        }
+    }
+}
+
+/// Runs `cargo test` in the task directory, streaming its output over the
+/// returned handle and ending with `Finished`.
+pub fn spawn_tests(task_path: &str) -> Result<TaskHandle, Box<dyn std::error::Error>> {
+    let full_path = task_directory(task_path)?;
+    Ok(spawn_handle(
+        move |sender, mut cancel_receiver| async move {
+            let mut stats = RunStats::default();
+            let mut test = Command::new("cargo");
+            test.arg("test").current_dir(&full_path);
+            let on_stdout = {
+                let sender = sender.clone();
+                move |line| {
+                    let _ = sender.send(TaskOutput::Stdout(line));
+                }
+            };
+            let on_stderr = {
+                let sender = sender.clone();
+                move |line| {
+                    let _ = sender.send(TaskOutput::Stderr(line));
+                }
+            };
+            match run_process(test, on_stdout, on_stderr, &mut cancel_receiver).await {
+                ProcessOutcome::Exited {
+                    status, elapsed, ..
+                } => {
+                    stats.run_time = Some(elapsed);
+                    let _ = sender.send(TaskOutput::Finished(status, stats));
+                }
+                ProcessOutcome::FailedToStart(e) => {
+                    let _ =
+                        sender.send(TaskOutput::Stderr(format!("Failed to start cargo: {}", e)));
+                    let _ = sender.send(TaskOutput::Finished(None, stats));
+                }
+                ProcessOutcome::Cancelled => {
+                    let _ = sender.send(TaskOutput::Cancelled(stats));
+                }
+            }
+        },
+    ))
+}
+
+/// Spawns `command`, passes each line of its stdout and stderr to the
+/// matching handler and waits for it to exit or for `cancel_receiver` to
+/// resolve.
+async fn run_process(
+    mut command: Command,
+    on_stdout: impl FnMut(String) + Send + 'static,
+    on_stderr: impl FnMut(String) + Send + 'static,
+    cancel_receiver: &mut oneshot::Receiver<()>,
+) -> ProcessOutcome {
+    let start = Instant::now();
+    let mut child = match command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return ProcessOutcome::FailedToStart(e),
+    };
+
+    let stdout_task = child
+        .stdout
+        .take()
+        .map(|stdout| tokio::spawn(forward_lines(stdout, on_stdout)));
+    let stderr_task = child
+        .stderr
+        .take()
+        .map(|stderr| tokio::spawn(forward_lines(stderr, on_stderr)));
+
+    tokio::select! {
+        (status, peak_rss_kb) = wait_for_exit(&mut child) => {
+            let elapsed = start.elapsed();
+            // Let the readers drain whatever is left in the pipes so the
+            // exit status is always the last message.
+            for task in [stdout_task, stderr_task].into_iter().flatten() {
+                let _ = task.await;
+            }
+            ProcessOutcome::Exited {
+                status: status.ok(),
+                elapsed,
+                peak_rss_kb,
+            }
+        }
+        _ = cancel_receiver => {
+            terminate(&mut child).await;
+            for task in [stdout_task, stderr_task].into_iter().flatten() {
+                task.abort();
+            }
+            ProcessOutcome::Cancelled
+        }
+    }
+}
+
+/// Waits for the child to exit and returns its status along with its peak
+/// resident set size in kilobytes, where the platform reports it.
+async fn wait_for_exit(child: &mut Child) -> (std::io::Result<ExitStatus>, Option<u64>) {
+    #[cfg(target_os = "linux")]
+    let peak_rss_kb = match child.id() {
+        Some(pid) => tokio::task::spawn_blocking(move || peak_rss_on_exit(pid))
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+    #[cfg(not(target_os = "linux"))]
+    let peak_rss_kb = None;
+
+    (child.wait().await, peak_rss_kb)
+}
+
+/// Blocks until the process exits, without reaping it, and returns its peak
+/// resident set size in kilobytes. The zombie is left for `Child::wait` to
+/// collect so that tokio still owns the child's lifecycle.
+#[cfg(target_os = "linux")]
+fn peak_rss_on_exit(pid: u32) -> Option<u64> {
+    // SAFETY: both out parameters are plain C structs that are valid when
+    // zeroed, and they outlive the call.
+    unsafe {
+        let mut info: libc::siginfo_t = std::mem::zeroed();
+        let mut usage: libc::rusage = std::mem::zeroed();
+        // The libc wrapper for waitid does not expose the rusage argument
+        // that the Linux syscall accepts, so call it directly.
+        let result = libc::syscall(
+            libc::SYS_waitid,
+            libc::P_PID,
+            pid as libc::id_t,
+            &mut info as *mut libc::siginfo_t,
+            libc::WEXITED | libc::WNOWAIT,
+            &mut usage as *mut libc::rusage,
+        );
+        (result == 0).then_some(usage.ru_maxrss as u64)
+    }
+}
+
+/// Reads `reader` until EOF, passing each line to `handler`. Invalid UTF-8
+/// is replaced rather than ending the stream.
+async fn forward_lines<R: AsyncRead + Unpin>(reader: R, mut handler: impl FnMut(String)) {
+    let mut reader = tokio::io::BufReader::new(reader);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let line = String::from_utf8_lossy(&buffer)
+            .trim_end_matches(['\n', '\r'])
+            .to_string();
+        handler(line);
+    }
+}
+
+/// Sends SIGTERM to the child and gives it `TERMINATE_GRACE_PERIOD` to exit
+/// before falling back to SIGKILL.
+async fn terminate(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: kill has no memory safety requirements; at worst the pid
+        // has already been reaped and the call fails with ESRCH.
+        unsafe {
+            libc::kill(pid as libc::pid_t, libc::SIGTERM);
+        }
+        if tokio::time::timeout(TERMINATE_GRACE_PERIOD, child.wait())
+            .await
+            .is_ok()
+        {
+            return;
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// Describes how a task exited, e.g. "exit code 101" or "killed by signal 9".
+pub fn describe_exit_status(status: &ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exit code {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("killed by signal {}", signal);
+        }
+    }
+    "unknown exit status".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modified_since_skips_inputs() {
+        let dir = std::env::temp_dir().join(format!("taskrunner-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("input")).unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        let after_source = SystemTime::now();
+        assert!(modified_since(&dir, after_source - Duration::from_secs(60)));
+        assert!(!modified_since(&dir, after_source));
+
+        std::thread::sleep(Duration::from_millis(10));
+        std::fs::write(dir.join("input/input.txt"), "1 2 3").unwrap();
+        std::fs::write(dir.join("notes.md"), "notes").unwrap();
+        assert!(!modified_since(&dir, after_source));
+        std::fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+        assert!(modified_since(&dir, after_source));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_solutions() {
+        assert_eq!(
+            parse_solutions("1 1\n1 2\n17 1\nnot a solution\n"),
+            BTreeSet::from([(1, 1), (1, 2), (17, 1)])
+        );
+    }
+}
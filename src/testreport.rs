@@ -0,0 +1,306 @@
+use crate::config::{Action, KeyBindings};
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// The outcome of a single test as reported by libtest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+pub struct TestCase {
+    /// The task the test belongs to, e.g. `day1/task1`.
+    pub task_path: String,
+    pub name: String,
+    pub outcome: TestOutcome,
+    /// The output libtest captured for the test, including its panic
+    /// message, if it failed.
+    pub failure: Vec<String>,
+    /// Whether the failure is shown under the test.
+    pub expanded: bool,
+}
+
+/// The results of `cargo test` for one or more tasks, built up from libtest's
+/// output a line at a time.
+#[derive(Default)]
+pub struct TestReport {
+    /// The tasks that have been tested, in order.
+    pub tasks: Vec<String>,
+    pub cases: Vec<TestCase>,
+    /// The selected test, as an index into `cases`.
+    pub selected: usize,
+    /// The failed test whose captured output is being read.
+    capturing: Option<usize>,
+}
+
+impl TestReport {
+    /// Attributes the tests that follow to a task.
+    pub fn start_task(&mut self, task_path: &str) {
+        self.tasks.push(task_path.to_string());
+        self.capturing = None;
+    }
+
+    /// Reads one line of libtest's stdout. Results come as lines such as
+    /// `test tests::test_part1 ... ok`, and once the tests are done the
+    /// output of each failed test follows under `---- <name> stdout ----`.
+    pub fn push_line(&mut self, line: &str) {
+        let Some(task_path) = self.tasks.last().cloned() else {
+            return;
+        };
+
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            self.finish_capture();
+            self.capturing = self
+                .cases
+                .iter()
+                .rposition(|case| case.task_path == task_path && case.name == name);
+            return;
+        }
+
+        if let Some((name, result)) = line
+            .strip_prefix("test ")
+            .and_then(|line| line.rsplit_once(" ... "))
+        {
+            let outcome = match result {
+                "ok" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                result if result.starts_with("ignored") => TestOutcome::Ignored,
+                _ => return,
+            };
+            self.cases.push(TestCase {
+                task_path,
+                name: name.to_string(),
+                outcome,
+                failure: Vec::new(),
+                expanded: false,
+            });
+            return;
+        }
+
+        if line == "failures:" || line.starts_with("test result:") {
+            self.finish_capture();
+            return;
+        }
+
+        if let Some(index) = self.capturing {
+            let failure = &mut self.cases[index].failure;
+            if !failure.is_empty() || !line.trim().is_empty() {
+                failure.push(line.to_string());
+            }
+        }
+    }
+
+    /// Stops reading a failed test's output, dropping the blank lines that
+    /// separate it from the next one.
+    fn finish_capture(&mut self) {
+        if let Some(index) = self.capturing.take() {
+            let failure = &mut self.cases[index].failure;
+            while failure.last().is_some_and(|line| line.trim().is_empty()) {
+                failure.pop();
+            }
+        }
+    }
+
+    pub fn failed(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome == TestOutcome::Failed)
+            .count()
+    }
+
+    /// Whether any test of the task failed.
+    pub fn has_failures(&self, task_path: &str) -> bool {
+        self.cases
+            .iter()
+            .any(|case| case.task_path == task_path && case.outcome == TestOutcome::Failed)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.cases.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Shows or hides the selected test's failure.
+    pub fn toggle_selected(&mut self) {
+        if let Some(case) = self.cases.get_mut(self.selected) {
+            case.expanded = !case.expanded && !case.failure.is_empty();
+        }
+    }
+
+    /// The index of the selected test's line in `lines`.
+    pub fn selected_line(&self) -> Option<usize> {
+        let selected = self.cases.get(self.selected)?;
+        let mut line = 0;
+        for task_path in &self.tasks {
+            line += 1;
+            for case in self
+                .cases
+                .iter()
+                .filter(|case| &case.task_path == task_path)
+            {
+                if std::ptr::eq(case, selected) {
+                    return Some(line);
+                }
+                line += 1;
+                if case.expanded {
+                    line += case.failure.len();
+                }
+            }
+        }
+        None
+    }
+
+    /// Renders a heading with the counts for each task, followed by its tests
+    /// and the failures that have been expanded.
+    pub fn lines(&self, keys: &KeyBindings) -> Vec<Line<'static>> {
+        if self.tasks.is_empty() {
+            return vec![Line::from(
+                keys.describe(&[Action::Test], "test the selected task or day"),
+            )];
+        }
+
+        let mut lines = Vec::new();
+        for task_path in &self.tasks {
+            let cases: Vec<(usize, &TestCase)> = self
+                .cases
+                .iter()
+                .enumerate()
+                .filter(|(_, case)| &case.task_path == task_path)
+                .collect();
+            let count = |outcome| cases.iter().filter(|(_, c)| c.outcome == outcome).count();
+            let failed = count(TestOutcome::Failed);
+            let color = if failed > 0 { Color::Red } else { Color::Green };
+            lines.push(Line::styled(
+                format!(
+                    "{}: {} passed, {} failed, {} ignored",
+                    task_path,
+                    count(TestOutcome::Passed),
+                    failed,
+                    count(TestOutcome::Ignored)
+                ),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+
+            for (index, case) in cases {
+                let (mark, color) = match case.outcome {
+                    TestOutcome::Passed => ("✓", Color::Green),
+                    TestOutcome::Failed => ("✗", Color::Red),
+                    TestOutcome::Ignored => ("-", Color::Yellow),
+                };
+                let mut name_style = Style::default();
+                if index == self.selected {
+                    name_style = name_style.add_modifier(Modifier::REVERSED);
+                }
+                let mut spans = vec![
+                    Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+                    Span::styled(case.name.clone(), name_style),
+                ];
+                if !case.failure.is_empty() && !case.expanded {
+                    spans.push(Span::raw(format!(
+                        " [{}]",
+                        keys.describe(&[Action::Toggle], "show failure")
+                    )));
+                }
+                lines.push(Line::from(spans));
+                if case.expanded {
+                    lines.extend(
+                        case.failure
+                            .iter()
+                            .map(|line| Line::styled(format!("      {}", line), Color::Red)),
+                    );
+                }
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 3 tests
+test tests::test_parse ... ok
+test tests::test_slow ... ignored, too slow
+test tests::test_part2 ... FAILED
+
+failures:
+
+---- tests::test_part2 stdout ----
+
+thread 'tests::test_part2' panicked at src/lib.rs:40:9:
+assertion `left == right` failed
+  left: 30
+ right: 31
+
+
+failures:
+    tests::test_part2
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out";
+
+    fn report() -> TestReport {
+        let mut report = TestReport::default();
+        report.start_task("day1/task1");
+        OUTPUT.lines().for_each(|line| report.push_line(line));
+        report
+    }
+
+    #[test]
+    fn test_push_line() {
+        let report = report();
+        let outcomes: Vec<(&str, TestOutcome)> = report
+            .cases
+            .iter()
+            .map(|case| (case.name.as_str(), case.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("tests::test_parse", TestOutcome::Passed),
+                ("tests::test_slow", TestOutcome::Ignored),
+                ("tests::test_part2", TestOutcome::Failed),
+            ]
+        );
+        assert_eq!(
+            report.cases[2].failure,
+            vec![
+                "thread 'tests::test_part2' panicked at src/lib.rs:40:9:",
+                "assertion `left == right` failed",
+                "  left: 30",
+                " right: 31",
+            ]
+        );
+        assert!(report.has_failures("day1/task1"));
+        assert!(!report.has_failures("day1/task2"));
+    }
+
+    #[test]
+    fn test_toggle_selected() {
+        let mut report = report();
+        report.toggle_selected();
+        assert!(!report.cases[0].expanded);
+        let collapsed = report.lines(&KeyBindings::default()).len();
+
+        report.select_next();
+        report.select_next();
+        report.select_next();
+        assert_eq!(report.selected, 2);
+        report.toggle_selected();
+        assert!(report.cases[2].expanded);
+        assert_eq!(report.lines(&KeyBindings::default()).len(), collapsed + 4);
+        assert_eq!(report.selected_line(), Some(3));
+    }
+}